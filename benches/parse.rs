@@ -0,0 +1,105 @@
+use bincode::{Decode, Encode};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fontori::{
+    table::{tags, LongHorMetric},
+    ttf::{font::Font, font_ref::FontRef},
+    utils::{
+        bincode::{decode_from_slice, encode_to_vec},
+        bytes::FixedSize,
+        reader::{ReadSeq, TryFromStream},
+        types::{EncodeSeq, Seq},
+    },
+};
+use std::{io::Cursor, path::Path};
+
+fn load_sfnt(name: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fonts")
+        .join(name);
+    let bytes = std::fs::read(path).unwrap();
+    let font = Font::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+
+    encode_to_vec(&font).unwrap()
+}
+
+fn parse_tables(c: &mut Criterion) {
+    let sfnt = load_sfnt("SourceCodePro-Medium.woff2");
+    let font = FontRef::new(&sfnt).unwrap();
+
+    c.bench_function("font", |b| {
+        b.iter(|| Font::try_from_stream(&mut Cursor::new(black_box(&sfnt))).unwrap())
+    });
+
+    for (name, tag) in [
+        ("glyf", tags::GLYF),
+        ("loca", tags::LOCA),
+        ("hmtx", tags::HMTX),
+    ] {
+        c.bench_function(name, |b| b.iter(|| font.table(black_box(tag)).unwrap()));
+    }
+}
+
+// not FixedSize, so sequences of it are encoded value by value
+#[derive(Encode)]
+struct PerElement<T>(T);
+
+impl<T: Encode> EncodeSeq for PerElement<T> {}
+
+// the per_element cases reproduce the bincode round trip per value that sequences used to go through
+fn compare_sequences<T>(c: &mut Criterion, name: &str, data: &[u8])
+where
+    T: FixedSize + Encode + Decode + Copy,
+{
+    let count = data.len() / T::FIXED_SIZE;
+
+    let mut group = c.benchmark_group(format!("read_seq/{name}"));
+    group.bench_function("per_element", |b| {
+        b.iter(|| {
+            let chunks = black_box(data).chunks_exact(T::FIXED_SIZE);
+            let values = chunks.map(|chunk| decode_from_slice::<T>(chunk).unwrap());
+            values.collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("bulk", |b| {
+        b.iter(|| {
+            let mut stream = Cursor::new(black_box(data));
+            stream.read_seq::<T>(count).unwrap()
+        })
+    });
+    group.finish();
+
+    let values = Cursor::new(data).read_seq::<T>(count).unwrap();
+    let per_element = values.iter().map(|&v| PerElement(v)).collect::<Seq<_>>();
+
+    let mut group = c.benchmark_group(format!("write_seq/{name}"));
+    group.bench_function("per_element", |b| {
+        b.iter(|| encode_to_vec(black_box(&per_element)).unwrap())
+    });
+    group.bench_function("bulk", |b| {
+        b.iter(|| encode_to_vec(black_box(&values)).unwrap())
+    });
+    group.finish();
+}
+
+fn sequences(c: &mut Criterion) {
+    let sfnt = load_sfnt("SourceCodePro-Medium.woff2");
+    let font = FontRef::new(&sfnt).unwrap();
+
+    compare_sequences::<u8>(c, "glyf", font.table_data(tags::GLYF).unwrap());
+
+    let loca = font.table_data(tags::LOCA).unwrap();
+    match font.head().unwrap().index_to_loc_format {
+        0 => compare_sequences::<u16>(c, "loca", loca),
+        _ => compare_sequences::<u32>(c, "loca", loca),
+    }
+
+    let hmtx = font.table_data(tags::HMTX).unwrap();
+    let long_metrics = font.hhea().unwrap().num_of_long_hor_metrics as usize;
+    let (h_metrics, left_side_bearings) = hmtx.split_at(long_metrics * LongHorMetric::FIXED_SIZE);
+    compare_sequences::<LongHorMetric>(c, "hmtx", h_metrics);
+    compare_sequences::<i16>(c, "hmtx_bearings", left_side_bearings);
+}
+
+criterion_group!(benches, parse_tables, sequences);
+criterion_main!(benches);
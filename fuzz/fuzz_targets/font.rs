@@ -0,0 +1,32 @@
+#![no_main]
+
+use fontori::{
+    table::GetFontTable,
+    ttf::{collection::FontCollection, font::Font},
+    utils::reader::TryFromStream,
+};
+use libfuzzer_sys::fuzz_target;
+use std::io::Cursor;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = FontCollection::try_from_stream(&mut Cursor::new(data));
+
+    let Ok(font) = Font::try_from_stream(&mut Cursor::new(data)) else {
+        return;
+    };
+
+    let _ = font.encode_sfnt();
+    let tables = font.font_tables();
+
+    if let Ok(cmap) = tables.cmap() {
+        for subtable in cmap.cmap_subtables.iter() {
+            subtable.mappings().for_each(drop);
+        }
+    }
+
+    if let (Ok(glyf), Ok(maxp)) = (tables.glyf(), tables.maxp()) {
+        for glyph_id in 0..glyf.num_glyphs() as u16 {
+            let _ = glyf.resolve(glyph_id, maxp);
+        }
+    }
+});
@@ -0,0 +1,29 @@
+#![no_main]
+
+use fontori::{ttf::font_ref::FontRef, validate::validate};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(font) = FontRef::new(data) else {
+        return;
+    };
+
+    validate(&font);
+
+    for tag in font.tags().collect::<Vec<_>>() {
+        let _ = font.table(tag);
+    }
+
+    if let Ok(cmap) = font.cmap() {
+        for c in ['\0', 'A', '\u{FFFF}', '\u{1F600}'] {
+            cmap.glyph_index(c);
+        }
+    }
+
+    if let (Ok(hmtx), Ok(glyf)) = (font.hmtx(), font.glyf()) {
+        for glyph_id in 0..glyf.num_glyphs() as u16 {
+            hmtx.metric(glyph_id);
+            let _ = glyf.glyph(glyph_id);
+        }
+    }
+});
@@ -0,0 +1,47 @@
+#![no_main]
+
+use fontori::{
+    table::{tags, FontTable},
+    ttf::font_dir::TableDirEntry,
+};
+use libfuzzer_sys::fuzz_target;
+use std::{collections::BTreeMap, io::Cursor};
+
+const TAGS: [u32; 12] = [
+    tags::CMAP,
+    tags::GLYF,
+    tags::HEAD,
+    tags::HHEA,
+    tags::HMTX,
+    tags::LOCA,
+    tags::MAXP,
+    tags::NAME,
+    tags::OS_2,
+    tags::POST,
+    tags::VHEA,
+    tags::VMTX,
+];
+
+// parses the data as the selected table, with the tables it depends on read from the same bytes
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, data)) = data.split_first() else {
+        return;
+    };
+
+    let mut stream = Cursor::new(data);
+    let mut tables = BTreeMap::new();
+    let tag = TAGS[selector as usize % TAGS.len()];
+
+    for tag in [tags::HEAD, tags::MAXP, tags::HHEA, tags::VHEA, tags::LOCA, tag] {
+        let entry = TableDirEntry {
+            tag,
+            check_sum: 0,
+            offset: 0,
+            length: data.len() as u32,
+        };
+
+        if let Ok(table) = FontTable::try_from_params(&entry, &tables, &mut stream) {
+            tables.insert(tag, table);
+        }
+    }
+});
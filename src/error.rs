@@ -1,3 +1,7 @@
+use crate::{
+    table::tags::{tag_to_string, Tag},
+    ttf::font::MAX_SFNT_SIZE,
+};
 use bincode::error::{DecodeError, EncodeError};
 use std::io;
 use thiserror::Error;
@@ -16,6 +20,73 @@ pub enum Error {
     ExpectedTable(&'static str),
     #[error("Expected cmap subtable '{0}'")]
     UnsupportedCmapSubtable(u16),
+    #[error("cmap subtable format {0} does not fit in 64 KiB")]
+    CmapSubtableTooLarge(u16),
+    #[error("Compound glyph components must be resolved through the glyf table")]
+    UnresolvedCompoundGlyph,
+    #[error("Glyph id {0} is out of range")]
+    InvalidGlyphId(u16),
+    #[error("Compound glyph component cycle through glyph {0}")]
+    ComponentCycle(u16),
+    #[error("Compound glyph nesting exceeds the maximum component depth of {0}")]
+    ComponentDepthExceeded(u16),
+    #[error("Compound glyph {0} expands to more points or components than a glyph can address")]
+    GlyphTooComplex(u16),
+    #[error("Component glyph {0} references a point that does not exist")]
+    InvalidComponentPoint(u16),
+    #[error("Fonts with zero units per em cannot be scaled")]
+    InvalidUnitsPerEm,
+    #[error("Table '{}' is required and cannot be removed", tag_to_string(*.0))]
+    RequiredTable(Tag),
+    #[error("Table '{}' cannot hold a table of a different kind", tag_to_string(*.0))]
+    MismatchedTable(Tag),
+    #[error("WOFF table '{}' does not match its original length", tag_to_string(*.0))]
+    InvalidWoffTable(Tag),
+    #[error("WOFF metadata does not match its original length")]
+    InvalidWoffMetadata,
+    #[error("Invalid WOFF2 data: {0}")]
+    InvalidWoff2Data(&'static str),
+    #[error("Invalid font collection: {0}")]
+    InvalidCollection(&'static str),
+    #[error("Font collections must be read with FontCollection")]
+    UnexpectedCollection,
+    #[error("WOFF and WOFF2 fonts must be decoded before they can be borrowed")]
+    CompressedFont,
+    #[error("Table '{}' is missing", tag_to_string(*.0))]
+    MissingTable(Tag),
+    #[error("Table '{}' extends past the end of the font data", tag_to_string(*.0))]
+    TableOutOfBounds(Tag),
+    #[error("Decoded font data of {0} bytes exceeds the limit of {MAX_SFNT_SIZE} bytes")]
+    DataTooLarge(u64),
+    #[error("Invalid '{}' table at offset {offset:#x}: {description}", tag_to_string(*.tag))]
+    InvalidTable {
+        tag: Tag,
+        offset: u64,
+        description: String,
+        source: Option<Box<Error>>,
+    },
+    #[error("Invalid glyph {glyph_id} in '{}' table at offset {offset:#x}: {description}", tag_to_string(*.tag))]
+    InvalidGlyph {
+        tag: Tag,
+        offset: u64,
+        glyph_id: u16,
+        description: String,
+        source: Option<Box<Error>>,
+    },
+}
+
+impl Error {
+    pub fn in_table(self, tag: Tag, offset: u64) -> Self {
+        match self {
+            Error::InvalidTable { .. } | Error::InvalidGlyph { .. } => self,
+            error => Error::InvalidTable {
+                tag,
+                offset,
+                description: error.to_string(),
+                source: Some(Box::new(error)),
+            },
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,5 +1,9 @@
 pub mod sfnt;
+pub mod subset;
 pub mod table;
 pub mod ttf;
 pub mod utils;
+pub mod validate;
+pub mod woff;
+pub mod woff2;
 pub mod error;
@@ -0,0 +1,389 @@
+use crate::{
+    error::Error,
+    table::{
+        glyph::{glyph_stats, Glyph, GlyphData},
+        tags::{self, Tag},
+        Cmap, FontTable, GetFontTable, Glyf, Hmtx, Loca, LocaFormat, LongHorMetric, LongVerMetric,
+        Maxp, PostV2, Vmtx,
+    },
+    ttf::font::Font,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+const GLYPH_DEPENDENT_TAGS: [Tag; 21] = [
+    tags::BASE,
+    tags::CBDT,
+    tags::CBLC,
+    tags::COLR,
+    tags::DSIG,
+    tags::EBDT,
+    tags::EBLC,
+    tags::EBSC,
+    tags::GDEF,
+    tags::GPOS,
+    tags::GSUB,
+    tags::GVAR,
+    tags::HDMX,
+    tags::HVAR,
+    tags::KERN,
+    tags::LTSH,
+    tags::MATH,
+    tags::SBIX,
+    tags::VMTX,
+    tags::VORG,
+    tags::VVAR,
+];
+
+// decides what happens to tables the subsetter cannot rewrite; the tables in
+// GLYPH_DEPENDENT_TAGS (GSUB, GPOS, GDEF, kern, COLR, bitmaps, variations and the
+// like) refer to glyph ids that no longer exist after a subset, so they are dropped
+// under either policy
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TablePolicy {
+    #[default]
+    Drop,
+    PassThrough,
+}
+
+#[derive(Debug, Default)]
+pub struct Subset {
+    chars: BTreeSet<char>,
+    glyph_ids: BTreeSet<u16>,
+    table_policy: TablePolicy,
+}
+
+impl Subset {
+    pub fn new(table_policy: TablePolicy) -> Self {
+        Self {
+            table_policy,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_chars<I>(&mut self, chars: I)
+    where
+        I: IntoIterator<Item = char>,
+    {
+        self.chars.extend(chars);
+    }
+
+    pub fn add_glyph_ids<I>(&mut self, glyph_ids: I)
+    where
+        I: IntoIterator<Item = u16>,
+    {
+        self.glyph_ids.extend(glyph_ids);
+    }
+
+    pub fn apply(&self, font: Font) -> Result<Font, Error> {
+        let plan = SubsetPlan::new(self, &font)?;
+        let scaler_type = font.scaler_type();
+        let mut tables = font.into_tables();
+        let mut subset = Font::new(scaler_type);
+
+        let glyphs = match tables.remove(&tags::GLYF) {
+            Some(FontTable::Glyf(glyf)) => plan.subset_glyphs(glyf),
+            _ => return Err(Error::ExpectedTable("glyf")),
+        };
+        let glyf = Glyf::new(glyphs.into_iter().map(Into::into).collect());
+        let mut offsets = glyf.offsets()?;
+        let format = match LocaFormat::Short.fits(offsets.as_slice()) {
+            true => LocaFormat::Short,
+            false => LocaFormat::Long,
+        };
+
+        for (tag, table) in tables {
+            let table = match table {
+                FontTable::Head(mut head) => {
+                    let [x_min, y_min, x_max, y_max] = bounds(&glyf);
+                    head.x_min = x_min;
+                    head.y_min = y_min;
+                    head.x_max = x_max;
+                    head.y_max = y_max;
+                    head.index_to_loc_format = (format == LocaFormat::Long) as i16;
+                    FontTable::Head(head)
+                }
+                FontTable::Hhea(mut hhea) => {
+                    let hmtx = Hmtx::from_metrics(&plan.metrics);
+                    hhea.num_of_long_hor_metrics = hmtx.h_metrics.as_slice().len() as u16;
+                    hhea.advance_width_max = plan
+                        .metrics
+                        .iter()
+                        .map(|m| m.advance_width)
+                        .max()
+                        .unwrap_or_default();
+                    FontTable::Hhea(hhea)
+                }
+                FontTable::Maxp(mut maxp) => {
+                    maxp.num_glyphs = plan.glyph_map.len() as u16;
+                    update_maxima(&mut maxp, &glyf)?;
+                    FontTable::Maxp(maxp)
+                }
+                FontTable::Hmtx(_) => FontTable::Hmtx(Hmtx::from_metrics(&plan.metrics)),
+                FontTable::Vhea(mut vhea) => {
+                    if let Some(metrics) = &plan.vertical_metrics {
+                        let vmtx = Vmtx::from_metrics(metrics);
+                        vhea.num_of_long_ver_metrics = vmtx.v_metrics.as_slice().len() as u16;
+                        vhea.advance_height_max = metrics
+                            .iter()
+                            .map(|m| m.advance_height)
+                            .max()
+                            .unwrap_or_default();
+                    }
+
+                    FontTable::Vhea(vhea)
+                }
+                FontTable::Vmtx(_) => match &plan.vertical_metrics {
+                    Some(metrics) => FontTable::Vmtx(Vmtx::from_metrics(metrics)),
+                    None => continue,
+                },
+                FontTable::Cmap(cmap) => {
+                    let mut subset_cmap = Cmap::from_mappings(&plan.mappings)?;
+
+                    if let Some(variations) = cmap.variation_sequences() {
+                        let code_points = plan.mappings.keys().copied().collect();
+                        let variations = variations.subset(&code_points, &plan.glyph_map);
+
+                        if !variations.var_selector_records.as_slice().is_empty() {
+                            subset_cmap.set_variation_sequences(variations);
+                        }
+                    }
+
+                    FontTable::Cmap(subset_cmap)
+                }
+                FontTable::Loca(_) => FontTable::Loca(Loca {
+                    offsets: std::mem::take(&mut offsets),
+                    format,
+                }),
+                FontTable::Post(mut post) => {
+                    if let Some(names) = &plan.glyph_names {
+                        post.set_glyph_names(PostV2::from_names(names.iter().map(String::as_str)));
+                    }
+
+                    FontTable::Post(post)
+                }
+                FontTable::Os2(mut os2) => {
+                    let first = plan.mappings.keys().next().copied().unwrap_or_default();
+                    let last = plan.mappings.keys().last().copied().unwrap_or_default();
+                    os2.header.us_first_char_index = first.min(u16::MAX as u32) as u16;
+                    os2.header.us_last_char_index = last.min(u16::MAX as u32) as u16;
+                    FontTable::Os2(os2)
+                }
+                FontTable::Other(_) if GLYPH_DEPENDENT_TAGS.contains(&tag) => continue,
+                FontTable::Other(_) if self.table_policy == TablePolicy::Drop => continue,
+                table => table,
+            };
+
+            subset.insert_table(tag, table)?;
+        }
+
+        subset.insert_table(tags::GLYF, FontTable::Glyf(glyf))?;
+
+        Ok(subset)
+    }
+}
+
+struct SubsetPlan {
+    glyph_map: BTreeMap<u16, u16>,
+    mappings: BTreeMap<u32, u16>,
+    metrics: Vec<LongHorMetric>,
+    vertical_metrics: Option<Vec<LongVerMetric>>,
+    glyph_names: Option<Vec<String>>,
+}
+
+impl SubsetPlan {
+    fn new(subset: &Subset, font: &Font) -> Result<Self, Error> {
+        let cmap = font.cmap()?;
+        let glyf = font.glyf()?;
+        let hmtx = font.hmtx()?;
+        let post = font.post()?;
+        let num_glyphs = glyf.num_glyphs();
+
+        if let Some(&glyph_id) = subset.glyph_ids.iter().find(|&&g| g as usize >= num_glyphs) {
+            return Err(Error::InvalidGlyphId(glyph_id));
+        }
+
+        let old_mappings = subset
+            .chars
+            .iter()
+            .filter_map(|&c| Some((c as u32, cmap.glyph_index(c)?)))
+            .filter(|&(_, glyph_id)| (glyph_id as usize) < num_glyphs)
+            .collect::<BTreeMap<_, _>>();
+
+        let mut glyph_ids = BTreeSet::from([0]);
+        glyph_ids.extend(&subset.glyph_ids);
+        glyph_ids.extend(old_mappings.values());
+
+        if let Some(variations) = cmap.variation_sequences() {
+            let code_points = old_mappings.keys().copied().collect();
+            let variant_glyphs = variations.variant_glyphs(&code_points);
+            glyph_ids.extend(variant_glyphs.filter(|&g| (g as usize) < num_glyphs));
+        }
+
+        let glyph_map = component_closure(glyf, glyph_ids)?
+            .into_iter()
+            .enumerate()
+            .map(|(new_id, old_id)| (old_id, new_id as u16))
+            .collect::<BTreeMap<_, _>>();
+
+        let mappings = old_mappings
+            .into_iter()
+            .map(|(code_point, glyph_id)| (code_point, glyph_map[&glyph_id]))
+            .collect();
+
+        let metrics = glyph_map
+            .keys()
+            .map(|&glyph_id| {
+                hmtx.metric(glyph_id).unwrap_or(LongHorMetric {
+                    advance_width: 0,
+                    left_side_bearing: 0,
+                })
+            })
+            .collect();
+
+        let vertical_metrics = font.vmtx().ok().map(|vmtx| {
+            glyph_map
+                .keys()
+                .map(|&glyph_id| {
+                    vmtx.metric(glyph_id).unwrap_or(LongVerMetric {
+                        advance_height: 0,
+                        top_side_bearing: 0,
+                    })
+                })
+                .collect()
+        });
+
+        let glyph_names = (post.num_glyph_names() > 0).then(|| {
+            glyph_map
+                .iter()
+                .map(|(&old_id, &new_id)| match post.glyph_name(old_id) {
+                    Some(name) => name.to_string(),
+                    None => format!("glyph{new_id}"),
+                })
+                .collect()
+        });
+
+        Ok(Self {
+            glyph_map,
+            mappings,
+            metrics,
+            vertical_metrics,
+            glyph_names,
+        })
+    }
+
+    fn subset_glyphs(&self, glyf: Glyf) -> Vec<Option<Glyph>> {
+        glyf.glyphs
+            .into_iter()
+            .enumerate()
+            .filter(|(glyph_id, _)| self.glyph_map.contains_key(&(*glyph_id as u16)))
+            .map(|(_, glyph)| {
+                let mut glyph = glyph.into_option()?;
+
+                if let GlyphData::Compound(compound) = &mut glyph.data {
+                    let components = std::mem::take(&mut compound.components);
+                    compound.components = components
+                        .into_iter()
+                        .map(|mut component| {
+                            component.glyph_index = self.glyph_map[&component.glyph_index];
+                            component
+                        })
+                        .collect();
+                }
+
+                Some(glyph)
+            })
+            .collect()
+    }
+}
+
+fn component_closure(glyf: &Glyf, glyph_ids: BTreeSet<u16>) -> Result<BTreeSet<u16>, Error> {
+    let mut closure = BTreeSet::new();
+    let mut pending = glyph_ids.into_iter().collect::<Vec<_>>();
+
+    while let Some(glyph_id) = pending.pop() {
+        if !closure.insert(glyph_id) {
+            continue;
+        }
+
+        let Some(GlyphData::Compound(compound)) = glyf.get(glyph_id).map(|g| &g.data) else {
+            continue;
+        };
+
+        for component in compound.components.iter() {
+            if component.glyph_index as usize >= glyf.num_glyphs() {
+                return Err(Error::InvalidGlyphId(component.glyph_index));
+            }
+
+            pending.push(component.glyph_index);
+        }
+    }
+
+    Ok(closure)
+}
+
+// the hinting limits describe the font program rather than the outlines, so they are kept
+fn update_maxima(maxp: &mut Maxp, glyf: &Glyf) -> Result<(), Error> {
+    let mut memo = vec![None; glyf.num_glyphs()];
+    let clamp = |value: u32| value.min(u16::MAX as u32) as u16;
+
+    maxp.max_points = 0;
+    maxp.max_contours = 0;
+    maxp.max_component_points = 0;
+    maxp.max_component_contours = 0;
+    maxp.max_size_of_instructions = 0;
+    maxp.max_component_elements = 0;
+    maxp.max_component_depth = 0;
+
+    for (glyph_id, glyph) in glyf.glyphs.iter().enumerate() {
+        let Some(glyph) = glyph.as_option() else {
+            continue;
+        };
+
+        let stats = glyph_stats(glyf, glyph_id as u16, &mut memo, &mut Vec::new())?;
+
+        match &glyph.data {
+            GlyphData::Simple(simple) => {
+                maxp.max_points = maxp.max_points.max(clamp(stats.points));
+                maxp.max_contours = maxp.max_contours.max(clamp(stats.contours));
+                maxp.max_size_of_instructions =
+                    maxp.max_size_of_instructions.max(simple.instruction_length);
+            }
+            GlyphData::Compound(compound) => {
+                let components = compound.components.as_slice().len() as u16;
+                let instructions = compound.instruction_length.as_option();
+
+                maxp.max_component_points = maxp.max_component_points.max(clamp(stats.points));
+                maxp.max_component_contours =
+                    maxp.max_component_contours.max(clamp(stats.contours));
+                maxp.max_size_of_instructions = maxp
+                    .max_size_of_instructions
+                    .max(instructions.copied().unwrap_or_default());
+                maxp.max_component_elements = maxp.max_component_elements.max(components);
+                maxp.max_component_depth = maxp.max_component_depth.max(stats.depth);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn bounds(glyf: &Glyf) -> [i16; 4] {
+    let headers = glyf
+        .glyphs
+        .iter()
+        .filter_map(|g| g.as_option())
+        .map(|g| &g.header);
+
+    headers
+        .fold(None, |bounds, h| {
+            let [x_min, y_min, x_max, y_max] =
+                bounds.unwrap_or([h.x_min, h.y_min, h.x_max, h.y_max]);
+            Some([
+                x_min.min(h.x_min),
+                y_min.min(h.y_min),
+                x_max.max(h.x_max),
+                y_max.max(h.y_max),
+            ])
+        })
+        .unwrap_or_default()
+}
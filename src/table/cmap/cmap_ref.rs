@@ -0,0 +1,159 @@
+use crate::{
+    error::Error,
+    table::{
+        cmap::{CMAP_HEADER_SIZE, ENCODING_RECORD_SIZE, PREFERRED_ENCODINGS},
+        CmapSubtable,
+    },
+    utils::{
+        bytes::{partition_point, read_be},
+        reader::TryFromStream,
+    },
+};
+use std::io::Cursor;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmapRef<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> CmapRef<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn number_subtables(&self) -> u16 {
+        read_be(self.data, 2).unwrap_or_default()
+    }
+
+    pub fn encodings(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        (0..self.number_subtables() as usize).map_while(|i| {
+            let record = CMAP_HEADER_SIZE + i * ENCODING_RECORD_SIZE;
+            Some((read_be(self.data, record)?, read_be(self.data, record + 2)?))
+        })
+    }
+
+    pub fn subtable(
+        &self,
+        platform_id: u16,
+        platform_specific_id: u16,
+    ) -> Option<CmapSubtableRef<'a>> {
+        let index = self
+            .encodings()
+            .position(|e| e == (platform_id, platform_specific_id))?;
+        let record = CMAP_HEADER_SIZE + index * ENCODING_RECORD_SIZE;
+        let offset = read_be::<u32>(self.data, record + 4)? as usize;
+
+        Some(CmapSubtableRef {
+            data: self.data.get(offset..)?,
+        })
+    }
+
+    pub fn best_subtable(&self) -> Option<CmapSubtableRef<'a>> {
+        PREFERRED_ENCODINGS
+            .iter()
+            .find_map(|&(platform_id, specific_id)| self.subtable(platform_id, specific_id))
+    }
+
+    pub fn glyph_index(&self, c: char) -> Option<u16> {
+        self.best_subtable()?.glyph_index(c as u32)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmapSubtableRef<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> CmapSubtableRef<'a> {
+    pub fn format(&self) -> Option<u16> {
+        read_be(self.data, 0)
+    }
+
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let glyph = match self.format()? {
+            0 => {
+                read_be::<u8>(self.data, 6 + u8::try_from(code_point).ok()? as usize).map(u16::from)
+            }
+            4 => self.format4_glyph_index(u16::try_from(code_point).ok()?),
+            6 => self.format6_glyph_index(code_point),
+            12 => self.group_glyph_index(code_point, true),
+            13 => self.group_glyph_index(code_point, false),
+            _ => self.decode().ok()?.glyph_index(code_point),
+        };
+
+        glyph.filter(|&g| g != 0)
+    }
+
+    pub fn decode(&self) -> Result<CmapSubtable, Error> {
+        CmapSubtable::try_from_stream(&mut Cursor::new(self.data))
+    }
+
+    fn format4_glyph_index(&self, code_point: u16) -> Option<u16> {
+        let seg_count_x2 = read_be::<u16>(self.data, 6)? as usize;
+        let seg_count = seg_count_x2 / 2;
+        let end_codes = 14;
+        let start_codes = end_codes + seg_count_x2 + 2;
+        let id_deltas = start_codes + seg_count_x2;
+        let id_range_offsets = id_deltas + seg_count_x2;
+
+        let segment = partition_point(seg_count, |i| {
+            read_be::<u16>(self.data, end_codes + i * 2).is_some_and(|end| end < code_point)
+        });
+
+        let start = read_be::<u16>(self.data, start_codes + segment * 2)?;
+        let id_delta = read_be::<u16>(self.data, id_deltas + segment * 2)?;
+        let range_offset_position = id_range_offsets + segment * 2;
+        let id_range_offset = read_be::<u16>(self.data, range_offset_position)?;
+
+        if segment >= seg_count || code_point < start {
+            return None;
+        }
+
+        match id_range_offset {
+            0 => Some(code_point.wrapping_add(id_delta)),
+            _ => {
+                let position = range_offset_position
+                    + id_range_offset as usize
+                    + (code_point - start) as usize * 2;
+
+                match read_be::<u16>(self.data, position)? {
+                    0 => None,
+                    glyph => Some(glyph.wrapping_add(id_delta)),
+                }
+            }
+        }
+    }
+
+    fn format6_glyph_index(&self, code_point: u32) -> Option<u16> {
+        let first_code = read_be::<u16>(self.data, 6)? as u32;
+        let entry_count = read_be::<u16>(self.data, 8)? as u32;
+        let index = code_point
+            .checked_sub(first_code)
+            .filter(|&i| i < entry_count)?;
+        read_be(self.data, 10 + index as usize * 2)
+    }
+
+    fn group_glyph_index(&self, code_point: u32, sequential: bool) -> Option<u16> {
+        let num_groups = read_be::<u32>(self.data, 12)? as usize;
+        let group_offset = |i: usize| 16 + i * 12;
+
+        let index = partition_point(num_groups, |i| {
+            read_be::<u32>(self.data, group_offset(i) + 4).is_some_and(|end| end < code_point)
+        });
+
+        let group = group_offset(index);
+        let start_char_code = read_be::<u32>(self.data, group)?;
+        let glyph_id = read_be::<u32>(self.data, group + 8)?;
+
+        if index >= num_groups || code_point < start_char_code {
+            return None;
+        }
+
+        let glyph = match sequential {
+            true => glyph_id.checked_add(code_point - start_char_code)?,
+            false => glyph_id,
+        };
+
+        u16::try_from(glyph).ok()
+    }
+}
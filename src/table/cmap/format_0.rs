@@ -0,0 +1,53 @@
+use crate::{
+    error::Error,
+    utils::{
+        bincode::decode_from_reader,
+        reader::{ReadSeq, TryFromStream},
+        types::Seq,
+    },
+};
+use bincode::Encode;
+use std::io::{Read, Seek};
+
+const GLYPH_COUNT: usize = 256;
+
+#[derive(Debug, Encode)]
+pub struct Format0 {
+    pub format: u16,
+    pub length: u16,
+    pub language: u16,
+    pub glyph_index_array: Seq<u8>,
+}
+
+impl TryFromStream for Format0 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let length = decode_from_reader(stream)?;
+        let language = decode_from_reader(stream)?;
+        let glyph_index_array = stream.read_seq(GLYPH_COUNT)?;
+
+        Ok(Self {
+            format: 0,
+            length,
+            language,
+            glyph_index_array,
+        })
+    }
+}
+
+impl Format0 {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let glyph = *self.glyph_index_array.as_slice().get(code_point as usize)?;
+        Some(glyph as u16).filter(|&g| g != 0)
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        self.glyph_index_array
+            .iter()
+            .enumerate()
+            .filter(|(_, &glyph)| glyph != 0)
+            .map(|(i, &glyph)| (i as u32, glyph as u16))
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    error::Error,
+    utils::{
+        bincode::decode_from_reader,
+        reader::{ReadSeq, TryFromStream},
+        types::{Padding, Seq},
+    },
+};
+use bincode::{enc::Encoder, error::EncodeError, Encode};
+use std::io::{Read, Seek};
+
+#[derive(Debug)]
+pub struct Format10 {
+    pub format: u16,
+    pub _reserved: Padding<2>,
+    pub length: u32,
+    pub language: u32,
+    pub start_char_code: u32,
+    pub num_chars: u32,
+    pub glyphs: Seq<u16>,
+}
+
+impl TryFromStream for Format10 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let _reserved = decode_from_reader(stream)?;
+        let length = decode_from_reader(stream)?;
+        let language = decode_from_reader(stream)?;
+        let start_char_code = decode_from_reader(stream)?;
+        let num_chars = decode_from_reader(stream)?;
+        let glyphs = stream.read_seq(num_chars as usize)?;
+
+        Ok(Self {
+            format: 10,
+            _reserved,
+            length,
+            language,
+            start_char_code,
+            num_chars,
+            glyphs,
+        })
+    }
+}
+
+impl Encode for Format10 {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.format.encode(encoder)?;
+        self._reserved.encode(encoder)?;
+        self.length.encode(encoder)?;
+        self.language.encode(encoder)?;
+        self.start_char_code.encode(encoder)?;
+        (self.glyphs.as_slice().len() as u32).encode(encoder)?;
+        self.glyphs.encode(encoder)
+    }
+}
+
+impl Format10 {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let index = code_point.checked_sub(self.start_char_code)?;
+        let glyph = *self.glyphs.as_slice().get(index as usize)?;
+        Some(glyph).filter(|&g| g != 0)
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        self.glyphs
+            .iter()
+            .enumerate()
+            .filter(|(_, &glyph)| glyph != 0)
+            .map(|(i, &glyph)| (self.start_char_code + i as u32, glyph))
+    }
+}
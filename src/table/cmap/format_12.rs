@@ -1,13 +1,21 @@
 use crate::{
     error::Error,
+    table::cmap::{unclaimed_ranges, MAX_CODE_POINT},
     utils::{
         bincode::decode_from_reader,
+        bytes::{read_be, write_be, FixedSize},
         reader::{ReadSeq, TryFromStream},
         types::{Padding, Seq},
     },
 };
 use bincode::{Decode, Encode};
-use std::io::{Read, Seek};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek},
+};
+
+const SUBTABLE_HEADER_SIZE: u32 = 16;
+const GROUP_SIZE: u32 = 12;
 
 #[derive(Debug, Encode)]
 pub struct Format12 {
@@ -47,3 +55,82 @@ pub struct Format12Group {
     pub end_char_code: u32,
     pub start_glyph_code: u32,
 }
+
+impl FixedSize for Format12Group {
+    const FIXED_SIZE: usize = GROUP_SIZE as usize;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            start_char_code: read_be(bytes, 0)?,
+            end_char_code: read_be(bytes, 4)?,
+            start_glyph_code: read_be(bytes, 8)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.start_char_code);
+        write_be(bytes, 4, &self.end_char_code);
+        write_be(bytes, 8, &self.start_glyph_code);
+    }
+}
+
+impl Format12 {
+    pub fn from_mappings(mappings: &BTreeMap<u32, u16>) -> Self {
+        let mut groups = Vec::<Format12Group>::new();
+
+        for (&code_point, &glyph) in mappings {
+            let glyph = glyph as u32;
+
+            match groups.last_mut() {
+                Some(group)
+                    if group.end_char_code + 1 == code_point
+                        && group.start_glyph_code + (code_point - group.start_char_code)
+                            == glyph =>
+                {
+                    group.end_char_code = code_point;
+                }
+                _ => groups.push(Format12Group {
+                    start_char_code: code_point,
+                    end_char_code: code_point,
+                    start_glyph_code: glyph,
+                }),
+            }
+        }
+
+        Self {
+            format: 12,
+            _reserved: [0; 2],
+            length: SUBTABLE_HEADER_SIZE + groups.len() as u32 * GROUP_SIZE,
+            language: 0,
+            n_groups: groups.len() as u32,
+            groups: groups.into(),
+        }
+    }
+
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let groups = self.groups.as_slice();
+        let index = groups.partition_point(|g| g.end_char_code < code_point);
+        let group = groups.get(index)?;
+        group.glyph_index(code_point)
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        let bounds = |g: &Format12Group| (g.start_char_code, g.end_char_code);
+
+        unclaimed_ranges(self.groups.as_slice(), bounds)
+            .flat_map(|(group, range)| range.filter_map(move |c| Some((c, group.glyph_index(c)?))))
+    }
+}
+
+impl Format12Group {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let offset = code_point.checked_sub(self.start_char_code)?;
+        let glyph = self.start_glyph_code.checked_add(offset)?;
+        u16::try_from(glyph).ok().filter(|&g| g != 0)
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        let end = self.end_char_code.min(MAX_CODE_POINT);
+        (self.start_char_code..=end).filter_map(|c| Some((c, self.glyph_index(c)?)))
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    error::Error,
+    table::cmap::unclaimed_ranges,
+    utils::{
+        bincode::decode_from_reader,
+        bytes::{read_be, write_be, FixedSize},
+        reader::{ReadSeq, TryFromStream},
+        types::{Padding, Seq},
+    },
+};
+use bincode::{enc::Encoder, error::EncodeError, Decode, Encode};
+use std::io::{Read, Seek};
+
+#[derive(Debug)]
+pub struct Format13 {
+    pub format: u16,
+    pub _reserved: Padding<2>,
+    pub length: u32,
+    pub language: u32,
+    pub n_groups: u32,
+    pub groups: Seq<Format13Group>,
+}
+
+impl TryFromStream for Format13 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let _reserved = decode_from_reader(stream)?;
+        let length = decode_from_reader(stream)?;
+        let language = decode_from_reader(stream)?;
+        let n_groups = decode_from_reader(stream)?;
+        let groups = stream.read_seq(n_groups as usize)?;
+
+        Ok(Self {
+            format: 13,
+            _reserved,
+            length,
+            language,
+            n_groups,
+            groups,
+        })
+    }
+}
+
+impl Encode for Format13 {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.format.encode(encoder)?;
+        self._reserved.encode(encoder)?;
+        self.length.encode(encoder)?;
+        self.language.encode(encoder)?;
+        (self.groups.as_slice().len() as u32).encode(encoder)?;
+        self.groups.encode(encoder)
+    }
+}
+
+impl Format13 {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let groups = self.groups.as_slice();
+        let index = groups.partition_point(|g| g.end_char_code < code_point);
+        let group = groups.get(index)?;
+
+        match group.start_char_code <= code_point {
+            true => group.glyph_index(),
+            false => None,
+        }
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        let bounds = |g: &Format13Group| (g.start_char_code, g.end_char_code);
+
+        unclaimed_ranges(self.groups.as_slice(), bounds).flat_map(|(group, range)| {
+            let glyph = group.glyph_index();
+            range.filter_map(move |c| Some((c, glyph?)))
+        })
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct Format13Group {
+    pub start_char_code: u32,
+    pub end_char_code: u32,
+    pub glyph_id: u32,
+}
+
+impl FixedSize for Format13Group {
+    const FIXED_SIZE: usize = 12;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            start_char_code: read_be(bytes, 0)?,
+            end_char_code: read_be(bytes, 4)?,
+            glyph_id: read_be(bytes, 8)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.start_char_code);
+        write_be(bytes, 4, &self.end_char_code);
+        write_be(bytes, 8, &self.glyph_id);
+    }
+}
+
+impl Format13Group {
+    fn glyph_index(&self) -> Option<u16> {
+        u16::try_from(self.glyph_id).ok().filter(|&g| g != 0)
+    }
+}
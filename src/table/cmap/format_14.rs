@@ -0,0 +1,414 @@
+use crate::{
+    error::Error,
+    utils::{
+        bincode::decode_from_reader,
+        bytes::{read_be, write_be, FixedSize},
+        reader::{ReadSeq, TryFromStream},
+        types::{Opt, Seq, U24},
+    },
+};
+use bincode::{enc::Encoder, error::EncodeError, Decode, Encode};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Read, Seek, SeekFrom},
+};
+
+const HEADER_SIZE: u32 = 10;
+const VAR_SELECTOR_RECORD_SIZE: u32 = 11;
+const UNICODE_RANGE_SIZE: u32 = 4;
+const UVS_MAPPING_SIZE: u32 = 5;
+
+#[derive(Debug)]
+pub struct Format14 {
+    pub format: u16,
+    pub length: u32,
+    pub num_var_selector_records: u32,
+    pub var_selector_records: Seq<VariationSelector>,
+}
+
+impl TryFromStream for Format14 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let start_pos = stream.stream_position()? - 2; // two bytes from the format
+        let length = decode_from_reader(stream)?;
+        let num_var_selector_records: u32 = decode_from_reader(stream)?;
+        let records =
+            stream.read_seq::<VariationSelectorRecord>(num_var_selector_records as usize)?;
+
+        let var_selector_records = records
+            .into_iter()
+            .map(|record| {
+                let default_uvs = match record.default_uvs_offset {
+                    0 => None,
+                    offset => {
+                        stream.seek(SeekFrom::Start(start_pos + offset as u64))?;
+                        Some(DefaultUvs::try_from_stream(stream)?)
+                    }
+                };
+
+                let non_default_uvs = match record.non_default_uvs_offset {
+                    0 => None,
+                    offset => {
+                        stream.seek(SeekFrom::Start(start_pos + offset as u64))?;
+                        Some(NonDefaultUvs::try_from_stream(stream)?)
+                    }
+                };
+
+                Ok(VariationSelector {
+                    var_selector: record.var_selector,
+                    default_uvs: default_uvs.into(),
+                    non_default_uvs: non_default_uvs.into(),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self {
+            format: 14,
+            length,
+            num_var_selector_records,
+            var_selector_records,
+        })
+    }
+}
+
+impl Encode for Format14 {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let selectors = self.var_selector_records.as_slice();
+        let mut offset = HEADER_SIZE + selectors.len() as u32 * VAR_SELECTOR_RECORD_SIZE;
+        let mut records = Vec::new();
+
+        for selector in selectors {
+            let default_uvs_offset = match selector.default_uvs.as_option() {
+                Some(uvs) => {
+                    let table_offset = offset;
+                    offset += 4 + uvs.ranges.as_slice().len() as u32 * UNICODE_RANGE_SIZE;
+                    table_offset
+                }
+                None => 0,
+            };
+
+            let non_default_uvs_offset = match selector.non_default_uvs.as_option() {
+                Some(uvs) => {
+                    let table_offset = offset;
+                    offset += 4 + uvs.uvs_mappings.as_slice().len() as u32 * UVS_MAPPING_SIZE;
+                    table_offset
+                }
+                None => 0,
+            };
+
+            records.push(VariationSelectorRecord {
+                var_selector: selector.var_selector,
+                default_uvs_offset,
+                non_default_uvs_offset,
+            });
+        }
+
+        self.format.encode(encoder)?;
+        offset.encode(encoder)?;
+        (selectors.len() as u32).encode(encoder)?;
+        Seq::from(records).encode(encoder)?;
+
+        for selector in selectors {
+            selector.default_uvs.encode(encoder)?;
+            selector.non_default_uvs.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Format14 {
+    pub fn variant_glyph(&self, base: char, selector: char) -> Option<VariantGlyph> {
+        let base = base as u32;
+        let selector = selector as u32;
+        let selectors = self.var_selector_records.as_slice();
+        let index = selectors
+            .binary_search_by_key(&selector, |s| s.var_selector.0)
+            .ok()?;
+        let record = &selectors[index];
+
+        if let Some(uvs) = record.default_uvs.as_option() {
+            if uvs.contains(base) {
+                return Some(VariantGlyph::Default);
+            }
+        }
+
+        let uvs = record.non_default_uvs.as_option()?;
+        uvs.glyph_index(base).map(VariantGlyph::Glyph)
+    }
+
+    pub fn variant_glyphs<'a>(
+        &'a self,
+        code_points: &'a BTreeSet<u32>,
+    ) -> impl Iterator<Item = u16> + 'a {
+        self.var_selector_records
+            .iter()
+            .filter_map(|s| s.non_default_uvs.as_option())
+            .flat_map(|uvs| uvs.uvs_mappings.iter())
+            .filter(|m| code_points.contains(&m.unicode_value.0))
+            .map(|m| m.glyph_id)
+    }
+
+    pub fn subset(&self, code_points: &BTreeSet<u32>, glyph_map: &BTreeMap<u16, u16>) -> Self {
+        let var_selector_records = self
+            .var_selector_records
+            .iter()
+            .filter_map(|selector| {
+                let default_uvs = selector
+                    .default_uvs
+                    .as_option()
+                    .map(|uvs| uvs.subset(code_points))
+                    .filter(|uvs| !uvs.ranges.as_slice().is_empty());
+                let non_default_uvs = selector
+                    .non_default_uvs
+                    .as_option()
+                    .map(|uvs| uvs.subset(code_points, glyph_map))
+                    .filter(|uvs| !uvs.uvs_mappings.as_slice().is_empty());
+
+                if default_uvs.is_none() && non_default_uvs.is_none() {
+                    return None;
+                }
+
+                Some(VariationSelector {
+                    var_selector: selector.var_selector,
+                    default_uvs: default_uvs.into(),
+                    non_default_uvs: non_default_uvs.into(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let length = var_selector_records.iter().fold(
+            HEADER_SIZE + var_selector_records.len() as u32 * VAR_SELECTOR_RECORD_SIZE,
+            |length, selector| {
+                let default_uvs = selector.default_uvs.as_option().map_or(0, |uvs| {
+                    4 + uvs.ranges.as_slice().len() as u32 * UNICODE_RANGE_SIZE
+                });
+                let non_default_uvs = selector.non_default_uvs.as_option().map_or(0, |uvs| {
+                    4 + uvs.uvs_mappings.as_slice().len() as u32 * UVS_MAPPING_SIZE
+                });
+
+                length + default_uvs + non_default_uvs
+            },
+        );
+
+        Self {
+            format: 14,
+            length,
+            num_var_selector_records: var_selector_records.len() as u32,
+            var_selector_records: var_selector_records.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantGlyph {
+    Default,
+    Glyph(u16),
+}
+
+#[derive(Debug)]
+pub struct VariationSelector {
+    pub var_selector: U24,
+    pub default_uvs: Opt<DefaultUvs>,
+    pub non_default_uvs: Opt<NonDefaultUvs>,
+}
+
+#[derive(Debug, Encode, Decode)]
+struct VariationSelectorRecord {
+    var_selector: U24,
+    default_uvs_offset: u32,
+    non_default_uvs_offset: u32,
+}
+
+impl FixedSize for VariationSelectorRecord {
+    const FIXED_SIZE: usize = VAR_SELECTOR_RECORD_SIZE as usize;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            var_selector: read_be(bytes, 0)?,
+            default_uvs_offset: read_be(bytes, 3)?,
+            non_default_uvs_offset: read_be(bytes, 7)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.var_selector);
+        write_be(bytes, 3, &self.default_uvs_offset);
+        write_be(bytes, 7, &self.non_default_uvs_offset);
+    }
+}
+
+#[derive(Debug)]
+pub struct DefaultUvs {
+    pub num_unicode_value_ranges: u32,
+    pub ranges: Seq<UnicodeRange>,
+}
+
+impl TryFromStream for DefaultUvs {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let num_unicode_value_ranges: u32 = decode_from_reader(stream)?;
+        let ranges = stream.read_seq(num_unicode_value_ranges as usize)?;
+
+        Ok(Self {
+            num_unicode_value_ranges,
+            ranges,
+        })
+    }
+}
+
+// counts are written from the sequences, so edited records stay consistent
+impl Encode for DefaultUvs {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        (self.ranges.as_slice().len() as u32).encode(encoder)?;
+        self.ranges.encode(encoder)
+    }
+}
+
+impl DefaultUvs {
+    pub fn contains(&self, code_point: u32) -> bool {
+        let ranges = self.ranges.as_slice();
+        let index = ranges.partition_point(|r| r.start_unicode_value.0 <= code_point);
+
+        index.checked_sub(1).is_some_and(|i| {
+            let range = &ranges[i];
+            code_point <= range.start_unicode_value.0 + range.additional_count as u32
+        })
+    }
+
+    fn subset(&self, code_points: &BTreeSet<u32>) -> Self {
+        let mut ranges = Vec::<UnicodeRange>::new();
+
+        let kept = self.ranges.iter().flat_map(|r| {
+            let start = r.start_unicode_value.0;
+            start..=start + r.additional_count as u32
+        });
+
+        for code_point in kept.filter(|c| code_points.contains(c)) {
+            match ranges.last_mut() {
+                Some(range)
+                    if range.additional_count < u8::MAX
+                        && range.start_unicode_value.0 + range.additional_count as u32 + 1
+                            == code_point =>
+                {
+                    range.additional_count += 1;
+                }
+                _ => ranges.push(UnicodeRange {
+                    start_unicode_value: U24(code_point),
+                    additional_count: 0,
+                }),
+            }
+        }
+
+        Self {
+            num_unicode_value_ranges: ranges.len() as u32,
+            ranges: ranges.into(),
+        }
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct UnicodeRange {
+    pub start_unicode_value: U24,
+    pub additional_count: u8,
+}
+
+impl FixedSize for UnicodeRange {
+    const FIXED_SIZE: usize = UNICODE_RANGE_SIZE as usize;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            start_unicode_value: read_be(bytes, 0)?,
+            additional_count: read_be(bytes, 3)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.start_unicode_value);
+        write_be(bytes, 3, &self.additional_count);
+    }
+}
+
+#[derive(Debug)]
+pub struct NonDefaultUvs {
+    pub num_uvs_mappings: u32,
+    pub uvs_mappings: Seq<UvsMapping>,
+}
+
+impl TryFromStream for NonDefaultUvs {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let num_uvs_mappings: u32 = decode_from_reader(stream)?;
+        let uvs_mappings = stream.read_seq(num_uvs_mappings as usize)?;
+
+        Ok(Self {
+            num_uvs_mappings,
+            uvs_mappings,
+        })
+    }
+}
+
+impl Encode for NonDefaultUvs {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        (self.uvs_mappings.as_slice().len() as u32).encode(encoder)?;
+        self.uvs_mappings.encode(encoder)
+    }
+}
+
+impl NonDefaultUvs {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let mappings = self.uvs_mappings.as_slice();
+        let index = mappings
+            .binary_search_by_key(&code_point, |m| m.unicode_value.0)
+            .ok()?;
+
+        Some(mappings[index].glyph_id)
+    }
+
+    fn subset(&self, code_points: &BTreeSet<u32>, glyph_map: &BTreeMap<u16, u16>) -> Self {
+        let uvs_mappings = self
+            .uvs_mappings
+            .iter()
+            .filter(|m| code_points.contains(&m.unicode_value.0))
+            .filter_map(|m| {
+                Some(UvsMapping {
+                    unicode_value: m.unicode_value,
+                    glyph_id: *glyph_map.get(&m.glyph_id)?,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            num_uvs_mappings: uvs_mappings.len() as u32,
+            uvs_mappings: uvs_mappings.into(),
+        }
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct UvsMapping {
+    pub unicode_value: U24,
+    pub glyph_id: u16,
+}
+
+impl FixedSize for UvsMapping {
+    const FIXED_SIZE: usize = UVS_MAPPING_SIZE as usize;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            unicode_value: read_be(bytes, 0)?,
+            glyph_id: read_be(bytes, 3)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.unicode_value);
+        write_be(bytes, 3, &self.glyph_id);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    error::Error,
+    utils::{
+        bincode::decode_from_reader,
+        bytes::{read_be, write_be, FixedSize},
+        reader::{ReadSeq, TryFromStream},
+        types::Seq,
+    },
+};
+use bincode::{Decode, Encode};
+use std::io::{Read, Seek};
+
+const SUB_HEADER_KEY_COUNT: usize = 256;
+const SUB_HEADER_SIZE: usize = 8;
+const HEADER_SIZE: usize = 6 + SUB_HEADER_KEY_COUNT * 2;
+
+#[derive(Debug, Encode)]
+pub struct Format2 {
+    pub format: u16,
+    pub length: u16,
+    pub language: u16,
+    pub sub_header_keys: Seq<u16>,
+    pub sub_headers: Seq<Format2SubHeader>,
+    pub glyph_index_array: Seq<u16>,
+}
+
+impl TryFromStream for Format2 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let length: u16 = decode_from_reader(stream)?;
+        let language = decode_from_reader(stream)?;
+        let sub_header_keys = stream.read_seq::<u16>(SUB_HEADER_KEY_COUNT)?;
+        let max_key = sub_header_keys.iter().max().copied().unwrap_or_default();
+        let sub_header_count = max_key as usize / SUB_HEADER_SIZE + 1;
+        let sub_headers = stream.read_seq(sub_header_count)?;
+        let array_start = HEADER_SIZE + sub_header_count * SUB_HEADER_SIZE;
+        let remaining_bytes = (length as usize).saturating_sub(array_start);
+        let glyph_index_array = stream.read_seq(remaining_bytes / 2)?;
+
+        Ok(Self {
+            format: 2,
+            length,
+            language,
+            sub_header_keys,
+            sub_headers,
+            glyph_index_array,
+        })
+    }
+}
+
+impl Format2 {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let code_point = u16::try_from(code_point).ok()?;
+        let [high, low] = code_point.to_be_bytes();
+
+        let (sub_header, byte) = match code_point {
+            0..=0xFF => match self.sub_header_index(low)? {
+                0 => (0, low),
+                _ => return None,
+            },
+            _ => match self.sub_header_index(high)? {
+                0 => return None,
+                index => (index, low),
+            },
+        };
+
+        self.sub_header_glyph_index(sub_header, byte)
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        (0..=0xFFu8).flat_map(move |high| {
+            let codes = match self.sub_header_index(high) {
+                Some(0) => vec![high as u32],
+                Some(_) => (0..=0xFFu32).map(|low| (high as u32) << 8 | low).collect(),
+                None => vec![],
+            };
+
+            codes
+                .into_iter()
+                .filter_map(|c| Some((c, self.glyph_index(c)?)))
+        })
+    }
+
+    fn sub_header_index(&self, byte: u8) -> Option<usize> {
+        let key = *self.sub_header_keys.as_slice().get(byte as usize)?;
+        Some(key as usize / SUB_HEADER_SIZE)
+    }
+
+    fn sub_header_glyph_index(&self, index: usize, byte: u8) -> Option<u16> {
+        let sub_header = self.sub_headers.as_slice().get(index)?;
+        let entry = (byte as u16).checked_sub(sub_header.first_code)?;
+
+        if entry >= sub_header.entry_count {
+            return None;
+        }
+
+        let sub_header_count = self.sub_headers.as_slice().len();
+        let range_offset_position = index * SUB_HEADER_SIZE + 6;
+        let array_offset = range_offset_position + sub_header.id_range_offset as usize;
+        let array_offset = array_offset.checked_sub(sub_header_count * SUB_HEADER_SIZE)?;
+        let array_index = array_offset / 2 + entry as usize;
+        let glyph = *self.glyph_index_array.as_slice().get(array_index)?;
+
+        match glyph {
+            0 => None,
+            _ => Some(glyph.wrapping_add(sub_header.id_delta as u16)).filter(|&g| g != 0),
+        }
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct Format2SubHeader {
+    pub first_code: u16,
+    pub entry_count: u16,
+    pub id_delta: i16,
+    pub id_range_offset: u16,
+}
+
+impl FixedSize for Format2SubHeader {
+    const FIXED_SIZE: usize = SUB_HEADER_SIZE;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            first_code: read_be(bytes, 0)?,
+            entry_count: read_be(bytes, 2)?,
+            id_delta: read_be(bytes, 4)?,
+            id_range_offset: read_be(bytes, 6)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.first_code);
+        write_be(bytes, 2, &self.entry_count);
+        write_be(bytes, 4, &self.id_delta);
+        write_be(bytes, 6, &self.id_range_offset);
+    }
+}
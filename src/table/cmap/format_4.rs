@@ -7,7 +7,13 @@ use crate::{
     },
 };
 use bincode::Encode;
-use std::io::{Read, Seek};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek},
+};
+
+const SUBTABLE_HEADER_SIZE: usize = 16;
+const SEGMENT_SIZE: usize = 8;
 
 #[derive(Debug, Encode)]
 pub struct Format4 {
@@ -31,7 +37,7 @@ impl TryFromStream for Format4 {
     where
         T: Read + Seek,
     {
-        let start_pos = stream.stream_position()? - 2; // two bytes from the format
+        let start_pos = stream.stream_position()?.saturating_sub(2); // two bytes from the format
         let length = decode_from_reader(stream)?;
         let language = decode_from_reader(stream)?;
         let seg_count_x2 = decode_from_reader(stream)?;
@@ -45,7 +51,7 @@ impl TryFromStream for Format4 {
         let id_delta = stream.read_seq(seg_count)?;
         let id_range_offset = stream.read_seq(seg_count)?;
         let current_pos = stream.stream_position()?;
-        let remaining_bytes = length - (current_pos - start_pos) as u16;
+        let remaining_bytes = (length as u64).saturating_sub(current_pos - start_pos);
         let glypth_count = remaining_bytes as usize / 2;
         let glyph_index_array = stream.read_seq(glypth_count)?;
 
@@ -66,3 +72,98 @@ impl TryFromStream for Format4 {
         })
     }
 }
+
+impl Format4 {
+    pub fn from_mappings(mappings: &BTreeMap<u16, u16>) -> Result<Self, Error> {
+        let mut segments = Vec::<(u16, u16, u16)>::new();
+
+        for (&code_point, &glyph) in mappings.range(..0xFFFF) {
+            match segments.last_mut() {
+                Some((start, end, first_glyph))
+                    if *end + 1 == code_point
+                        && first_glyph.wrapping_add(code_point - *start) == glyph =>
+                {
+                    *end = code_point;
+                }
+                _ => segments.push((code_point, code_point, glyph)),
+            }
+        }
+
+        // the terminal segment maps 0xFFFF to .notdef through an idDelta of 1
+        segments.push((0xFFFF, 0xFFFF, 0));
+
+        let seg_count = segments.len();
+        let entry_selector = seg_count.ilog2();
+        let search_range = 2 << entry_selector;
+        let length = SUBTABLE_HEADER_SIZE + seg_count * SEGMENT_SIZE;
+
+        let Ok(length) = u16::try_from(length) else {
+            return Err(Error::CmapSubtableTooLarge(4));
+        };
+
+        Ok(Self {
+            format: 4,
+            length,
+            language: 0,
+            seg_count_x2: (seg_count * 2) as u16,
+            search_range: search_range as u16,
+            entry_selector: entry_selector as u16,
+            range_shift: (seg_count * 2 - search_range) as u16,
+            end_code: segments.iter().map(|s| s.1).collect(),
+            _reserved: [0; 2],
+            start_code: segments.iter().map(|s| s.0).collect(),
+            id_delta: segments.iter().map(|s| s.2.wrapping_sub(s.0)).collect(),
+            id_range_offset: segments.iter().map(|_| 0).collect(),
+            glyph_index_array: Seq::default(),
+        })
+    }
+
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let code_point = u16::try_from(code_point).ok()?;
+        let end_code = self.end_code.as_slice();
+        let segment = end_code.partition_point(|&end| end < code_point);
+        self.segment_glyph_index(segment, code_point)
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        let segments = self.start_code.iter().zip(self.end_code.iter()).enumerate();
+        let mut next = 0;
+
+        // overlapping segments are malformed, so each code point is only taken from the first one
+        segments.flat_map(move |(segment, (&start, &end))| {
+            let start = next.max(start as u32);
+            next = next.max(end as u32 + 1);
+            (start..next)
+                .filter(|&c| c != 0xFFFF)
+                .filter_map(move |c| Some((c, self.segment_glyph_index(segment, c as u16)?)))
+        })
+    }
+
+    fn segment_glyph_index(&self, segment: usize, code_point: u16) -> Option<u16> {
+        let start = *self.start_code.as_slice().get(segment)?;
+        let id_delta = *self.id_delta.as_slice().get(segment)?;
+        let id_range_offset = *self.id_range_offset.as_slice().get(segment)?;
+
+        if code_point < start {
+            return None;
+        }
+
+        let glyph = match id_range_offset {
+            0 => code_point.wrapping_add(id_delta),
+            _ => {
+                let seg_count = self.end_code.as_slice().len();
+                let index =
+                    (id_range_offset / 2) as usize + (code_point - start) as usize + segment;
+                let index = index.checked_sub(seg_count)?;
+                let glyph = *self.glyph_index_array.as_slice().get(index)?;
+
+                match glyph {
+                    0 => 0,
+                    _ => glyph.wrapping_add(id_delta),
+                }
+            }
+        };
+
+        Some(glyph).filter(|&g| g != 0)
+    }
+}
@@ -40,3 +40,19 @@ impl TryFromStream for Format6 {
         })
     }
 }
+
+impl Format6 {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let index = code_point.checked_sub(self.first_code as u32)?;
+        let glyph = *self.glyph_index_array.as_slice().get(index as usize)?;
+        Some(glyph).filter(|&g| g != 0)
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        self.glyph_index_array
+            .iter()
+            .enumerate()
+            .filter(|(_, &glyph)| glyph != 0)
+            .map(|(i, &glyph)| (self.first_code as u32 + i as u32, glyph))
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    error::Error,
+    table::cmap::{format_12::Format12Group, unclaimed_ranges},
+    utils::{
+        bincode::decode_from_reader,
+        reader::{ReadSeq, TryFromStream},
+        types::{Padding, Seq},
+    },
+};
+use bincode::{enc::Encoder, error::EncodeError, Encode};
+use std::io::{Read, Seek};
+
+const IS32_LENGTH: usize = 8192;
+
+#[derive(Debug)]
+pub struct Format8 {
+    pub format: u16,
+    pub _reserved: Padding<2>,
+    pub length: u32,
+    pub language: u32,
+    pub is32: Seq<u8>,
+    pub n_groups: u32,
+    pub groups: Seq<Format12Group>,
+}
+
+impl TryFromStream for Format8 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let _reserved = decode_from_reader(stream)?;
+        let length = decode_from_reader(stream)?;
+        let language = decode_from_reader(stream)?;
+        let is32 = stream.read_seq(IS32_LENGTH)?;
+        let n_groups = decode_from_reader(stream)?;
+        let groups = stream.read_seq(n_groups as usize)?;
+
+        Ok(Self {
+            format: 8,
+            _reserved,
+            length,
+            language,
+            is32,
+            n_groups,
+            groups,
+        })
+    }
+}
+
+impl Encode for Format8 {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.format.encode(encoder)?;
+        self._reserved.encode(encoder)?;
+        self.length.encode(encoder)?;
+        self.language.encode(encoder)?;
+        self.is32.encode(encoder)?;
+        (self.groups.as_slice().len() as u32).encode(encoder)?;
+        self.groups.encode(encoder)
+    }
+}
+
+impl Format8 {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let groups = self.groups.as_slice();
+        let index = groups.partition_point(|g| g.end_char_code < code_point);
+        groups.get(index)?.glyph_index(code_point)
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        let bounds = |g: &Format12Group| (g.start_char_code, g.end_char_code);
+
+        unclaimed_ranges(self.groups.as_slice(), bounds)
+            .flat_map(|(group, range)| range.filter_map(move |c| Some((c, group.glyph_index(c)?))))
+    }
+
+    pub fn is_32_bit_high_word(&self, value: u16) -> bool {
+        let byte = self.is32.as_slice()[value as usize / 8];
+        byte & (0x80 >> (value % 8)) != 0
+    }
+}
@@ -1,24 +1,53 @@
+mod cmap_ref;
+mod format_0;
+mod format_10;
 mod format_12;
+mod format_13;
+mod format_14;
+mod format_2;
 mod format_4;
 mod format_6;
+mod format_8;
 
-pub use {format_12::Format12, format_4::Format4, format_6::Format6};
+pub use {
+    cmap_ref::{CmapRef, CmapSubtableRef},
+    format_0::Format0,
+    format_10::Format10,
+    format_12::{Format12, Format12Group},
+    format_13::{Format13, Format13Group},
+    format_14::{
+        DefaultUvs, Format14, NonDefaultUvs, UnicodeRange, UvsMapping, VariantGlyph,
+        VariationSelector,
+    },
+    format_2::{Format2, Format2SubHeader},
+    format_4::Format4,
+    format_6::Format6,
+    format_8::Format8,
+};
 
 use crate::{
     error::Error,
+    table::tags,
     utils::{
-        bincode::decode_from_reader,
+        bincode::{decode_from_reader, encode_to_vec},
+        bytes::{read_be, write_be, FixedSize},
         reader::{ReadSeq, TryFromStream},
         types::Seq,
     },
 };
 use bincode::{enc::Encoder, error::EncodeError, Decode, Encode};
 use std::{
-    collections::HashSet,
-    io::{Read, Seek},
+    collections::BTreeMap,
+    io::{Read, Seek, SeekFrom},
+    ops::Range,
 };
 
-#[derive(Debug, Encode)]
+const CMAP_HEADER_SIZE: usize = 4;
+const ENCODING_RECORD_SIZE: usize = 8;
+const MAX_CODE_POINT: u32 = 0x10FFFF;
+const PREFERRED_ENCODINGS: [(u16, u16); 4] = [(3, 10), (0, 4), (3, 1), (0, 3)];
+
+#[derive(Debug)]
 pub struct Cmap {
     pub index: CmapHeader,
     pub encoding_subtables: Seq<EncodingSubtable>,
@@ -30,46 +59,266 @@ impl TryFromStream for Cmap {
     where
         R: Read + Seek,
     {
+        let table_start = stream.stream_position()?;
         let index = CmapHeader::try_from_stream(stream)?;
         let number_subtables = index.number_subtables.into();
-        let encoding_subtables = stream.read_seq::<EncodingSubtable>(number_subtables)?;
+        let encoding_records = stream.read_seq::<EncodingRecord>(number_subtables)?;
+        let mut offsets = Vec::new();
+        let mut encoding_subtables = Vec::new();
 
-        let offsets = encoding_subtables
-            .iter()
-            .map(|t| t.offset)
-            .collect::<HashSet<_>>();
+        for record in encoding_records {
+            let subtable_index = match offsets.iter().position(|&o| o == record.offset) {
+                Some(index) => index,
+                None => {
+                    offsets.push(record.offset);
+                    offsets.len() - 1
+                }
+            };
+
+            encoding_subtables.push(EncodingSubtable {
+                platform_id: record.platform_id,
+                platform_specific_id: record.platform_specific_id,
+                subtable_index,
+            });
+        }
 
         let cmap_subtables = offsets
             .into_iter()
-            .map(|_| CmapSubtable::try_from_stream(stream))
+            .map(|offset| {
+                let subtable_start = table_start + offset as u64;
+                stream.seek(SeekFrom::Start(subtable_start))?;
+                CmapSubtable::try_from_stream(stream)
+                    .map_err(|e| e.in_table(tags::CMAP, subtable_start))
+            })
             .collect::<Result<_, _>>()?;
 
         Ok(Self {
             index,
-            encoding_subtables,
+            encoding_subtables: encoding_subtables.into(),
             cmap_subtables,
         })
     }
 }
 
+impl Encode for Cmap {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let number_subtables = self.encoding_subtables.as_slice().len();
+        let header_size = CMAP_HEADER_SIZE + number_subtables * ENCODING_RECORD_SIZE;
+        let mut offsets = Vec::new();
+        let mut subtables = Vec::new();
+
+        for subtable in self.cmap_subtables.iter() {
+            let bytes = encode_to_vec(subtable)?;
+            offsets.push((header_size + subtables.len()) as u32);
+            subtables.extend(bytes);
+        }
+
+        self.index.version.encode(encoder)?;
+        (number_subtables as u16).encode(encoder)?;
+
+        for subtable in self.encoding_subtables.iter() {
+            let offset = offsets
+                .get(subtable.subtable_index)
+                .ok_or(EncodeError::Other("cmap subtable index out of range"))?;
+
+            EncodingRecord {
+                platform_id: subtable.platform_id,
+                platform_specific_id: subtable.platform_specific_id,
+                offset: *offset,
+            }
+            .encode(encoder)?;
+        }
+
+        Seq::from(subtables).encode(encoder)
+    }
+}
+
+impl Cmap {
+    pub fn from_mappings(mappings: &BTreeMap<u32, u16>) -> Result<Self, Error> {
+        let bmp = mappings
+            .iter()
+            .filter_map(|(&c, &g)| Some((u16::try_from(c).ok()?, g)))
+            .collect();
+        let supplementary = mappings.keys().any(|&c| c > u16::MAX as u32);
+
+        let (encoding_subtables, cmap_subtables) = match Format4::from_mappings(&bmp) {
+            Ok(format_4) if !supplementary => (
+                vec![
+                    EncodingSubtable::new(0, 3, 0),
+                    EncodingSubtable::new(3, 1, 0),
+                ],
+                vec![CmapSubtable::Format4(format_4)],
+            ),
+            Ok(format_4) => (
+                vec![
+                    EncodingSubtable::new(0, 3, 0),
+                    EncodingSubtable::new(0, 4, 1),
+                    EncodingSubtable::new(3, 1, 0),
+                    EncodingSubtable::new(3, 10, 1),
+                ],
+                vec![
+                    CmapSubtable::Format4(format_4),
+                    CmapSubtable::Format12(Format12::from_mappings(mappings)),
+                ],
+            ),
+            // too many segments for format 4, so only the 32-bit subtable is written
+            Err(Error::CmapSubtableTooLarge(_)) => (
+                vec![
+                    EncodingSubtable::new(0, 4, 0),
+                    EncodingSubtable::new(3, 10, 0),
+                ],
+                vec![CmapSubtable::Format12(Format12::from_mappings(mappings))],
+            ),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            index: CmapHeader {
+                version: 0,
+                number_subtables: encoding_subtables.len() as u16,
+            },
+            encoding_subtables: encoding_subtables.into(),
+            cmap_subtables: cmap_subtables.into(),
+        })
+    }
+
+    pub fn subtable(&self, encoding: &EncodingSubtable) -> Option<&CmapSubtable> {
+        self.cmap_subtables.as_slice().get(encoding.subtable_index)
+    }
+
+    pub fn best_subtable(&self) -> Option<&CmapSubtable> {
+        PREFERRED_ENCODINGS
+            .iter()
+            .find_map(|&(platform_id, specific_id)| {
+                self.encoding_subtables
+                    .iter()
+                    .find(|t| t.platform_id == platform_id && t.platform_specific_id == specific_id)
+                    .and_then(|t| self.subtable(t))
+            })
+    }
+
+    pub fn glyph_index(&self, c: char) -> Option<u16> {
+        self.best_subtable()?.glyph_index(c as u32)
+    }
+
+    pub fn variation_sequences(&self) -> Option<&Format14> {
+        self.cmap_subtables.iter().find_map(|t| match t {
+            CmapSubtable::Format14(table) => Some(table),
+            _ => None,
+        })
+    }
+
+    // records stay sorted by platform and encoding, so (0, 5) goes ahead of the Windows ones
+    pub fn set_variation_sequences(&mut self, variations: Format14) {
+        let mut encoding_subtables = std::mem::take(&mut self.encoding_subtables).into_vec();
+        let mut cmap_subtables = std::mem::take(&mut self.cmap_subtables).into_vec();
+
+        match encoding_subtables
+            .iter()
+            .position(|t| (t.platform_id, t.platform_specific_id) == (0, 5))
+        {
+            Some(index) => {
+                cmap_subtables[encoding_subtables[index].subtable_index] =
+                    CmapSubtable::Format14(variations)
+            }
+            None => {
+                let index = encoding_subtables
+                    .iter()
+                    .position(|t| (t.platform_id, t.platform_specific_id) > (0, 5))
+                    .unwrap_or(encoding_subtables.len());
+                encoding_subtables.insert(index, EncodingSubtable::new(0, 5, cmap_subtables.len()));
+                cmap_subtables.push(CmapSubtable::Format14(variations));
+            }
+        }
+
+        self.index.number_subtables = encoding_subtables.len() as u16;
+        self.encoding_subtables = encoding_subtables.into();
+        self.cmap_subtables = cmap_subtables.into();
+    }
+
+    pub fn variant_glyph(&self, base: char, selector: char) -> Option<u16> {
+        let variants = self.variation_sequences()?;
+
+        match variants.variant_glyph(base, selector)? {
+            VariantGlyph::Default => self.glyph_index(base),
+            VariantGlyph::Glyph(glyph) => Some(glyph),
+        }
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, u16)> + '_ {
+        self.best_subtable().into_iter().flat_map(|t| t.mappings())
+    }
+
+    pub fn reverse_mappings(&self) -> BTreeMap<u16, Vec<u32>> {
+        let mut glyphs = BTreeMap::<u16, Vec<u32>>::new();
+
+        for (code_point, glyph) in self.mappings() {
+            glyphs.entry(glyph).or_default().push(code_point);
+        }
+
+        glyphs
+    }
+}
+
 #[derive(Debug, Encode, Decode)]
 pub struct CmapHeader {
     pub version: u16,
     pub number_subtables: u16,
 }
 
-#[derive(Debug, Encode, Decode)]
+#[derive(Debug)]
 pub struct EncodingSubtable {
     pub platform_id: u16,
     pub platform_specific_id: u16,
-    pub offset: u32,
+    pub subtable_index: usize,
+}
+
+impl EncodingSubtable {
+    pub fn new(platform_id: u16, platform_specific_id: u16, subtable_index: usize) -> Self {
+        Self {
+            platform_id,
+            platform_specific_id,
+            subtable_index,
+        }
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+struct EncodingRecord {
+    platform_id: u16,
+    platform_specific_id: u16,
+    offset: u32,
+}
+
+impl FixedSize for EncodingRecord {
+    const FIXED_SIZE: usize = ENCODING_RECORD_SIZE;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            platform_id: read_be(bytes, 0)?,
+            platform_specific_id: read_be(bytes, 2)?,
+            offset: read_be(bytes, 4)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.platform_id);
+        write_be(bytes, 2, &self.platform_specific_id);
+        write_be(bytes, 4, &self.offset);
+    }
 }
 
 #[derive(Debug)]
 pub enum CmapSubtable {
+    Format0(Format0),
+    Format2(Format2),
     Format4(Format4),
     Format6(Format6),
+    Format8(Format8),
+    Format10(Format10),
     Format12(Format12),
+    Format13(Format13),
+    Format14(Format14),
 }
 
 impl TryFromStream for CmapSubtable {
@@ -80,20 +329,77 @@ impl TryFromStream for CmapSubtable {
         let format: u16 = decode_from_reader(stream)?;
 
         match format {
+            0 => Format0::try_from_stream(stream).map(Self::Format0),
+            2 => Format2::try_from_stream(stream).map(Self::Format2),
             4 => Format4::try_from_stream(stream).map(Self::Format4),
             6 => Format6::try_from_stream(stream).map(Self::Format6),
+            8 => Format8::try_from_stream(stream).map(Self::Format8),
+            10 => Format10::try_from_stream(stream).map(Self::Format10),
             12 => Format12::try_from_stream(stream).map(Self::Format12),
+            13 => Format13::try_from_stream(stream).map(Self::Format13),
+            14 => Format14::try_from_stream(stream).map(Self::Format14),
             _ => Err(Error::UnsupportedCmapSubtable(format)),
         }
     }
 }
 
+impl CmapSubtable {
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        match self {
+            CmapSubtable::Format0(table) => table.glyph_index(code_point),
+            CmapSubtable::Format2(table) => table.glyph_index(code_point),
+            CmapSubtable::Format4(table) => table.glyph_index(code_point),
+            CmapSubtable::Format6(table) => table.glyph_index(code_point),
+            CmapSubtable::Format8(table) => table.glyph_index(code_point),
+            CmapSubtable::Format10(table) => table.glyph_index(code_point),
+            CmapSubtable::Format12(table) => table.glyph_index(code_point),
+            CmapSubtable::Format13(table) => table.glyph_index(code_point),
+            CmapSubtable::Format14(_) => None,
+        }
+    }
+
+    pub fn mappings(&self) -> Box<dyn Iterator<Item = (u32, u16)> + '_> {
+        match self {
+            CmapSubtable::Format0(table) => Box::new(table.mappings()),
+            CmapSubtable::Format2(table) => Box::new(table.mappings()),
+            CmapSubtable::Format4(table) => Box::new(table.mappings()),
+            CmapSubtable::Format6(table) => Box::new(table.mappings()),
+            CmapSubtable::Format8(table) => Box::new(table.mappings()),
+            CmapSubtable::Format10(table) => Box::new(table.mappings()),
+            CmapSubtable::Format12(table) => Box::new(table.mappings()),
+            CmapSubtable::Format13(table) => Box::new(table.mappings()),
+            CmapSubtable::Format14(_) => Box::new(std::iter::empty()),
+        }
+    }
+}
+
 impl Encode for CmapSubtable {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         match self {
+            CmapSubtable::Format0(table) => table.encode(encoder),
+            CmapSubtable::Format2(table) => table.encode(encoder),
             CmapSubtable::Format4(table) => table.encode(encoder),
             CmapSubtable::Format6(table) => table.encode(encoder),
+            CmapSubtable::Format8(table) => table.encode(encoder),
+            CmapSubtable::Format10(table) => table.encode(encoder),
             CmapSubtable::Format12(table) => table.encode(encoder),
+            CmapSubtable::Format13(table) => table.encode(encoder),
+            CmapSubtable::Format14(table) => table.encode(encoder),
         }
     }
 }
+
+// malformed groups can overlap, so each code point is only taken from the first group
+fn unclaimed_ranges<G>(
+    groups: &[G],
+    bounds: fn(&G) -> (u32, u32),
+) -> impl Iterator<Item = (&G, Range<u32>)> {
+    let mut next = 0;
+
+    groups.iter().map(move |group| {
+        let (start, end) = bounds(group);
+        let start = next.max(start);
+        next = next.max(end.min(MAX_CODE_POINT) + 1);
+        (group, start..next)
+    })
+}
@@ -1,21 +1,33 @@
 use crate::{
     error::Error,
-    table::{glyph::Glyph, FontTable, GetFontTable, Tag},
-    utils::{reader::TryFromStream, types::Seq},
+    table::{
+        glyph::{Glyph, GlyphHeader, GlyphOutline, GlyphResolver, OutlinePen, ResolvedGlyph},
+        tags, FontTable, GetFontTable, LocaRef, Maxp, Tag,
+    },
+    utils::{
+        bincode::encode_to_vec,
+        bytes::read_be,
+        reader::TryFromStream,
+        types::{Opt, Seq},
+    },
 };
-use bincode::Encode;
+use bincode::{enc::Encoder, error::EncodeError, Encode};
 use std::{
     collections::BTreeMap,
-    io::{Read, Seek, SeekFrom},
+    io::{Cursor, Read, Seek, SeekFrom},
 };
 
-#[derive(Debug, Encode)]
+const ALIGNMENT: usize = 4;
+
+#[derive(Debug)]
 pub struct Glyf {
-    pub glyphs: Seq<Glyph>,
+    pub glyphs: Seq<Opt<Glyph>>,
+    pub alignment: usize,
 }
 
 impl Glyf {
     pub fn try_from_params<T>(
+        length: u32,
         tables: &BTreeMap<Tag, FontTable>,
         stream: &mut T,
     ) -> Result<Self, Error>
@@ -26,21 +38,161 @@ impl Glyf {
         let table_offset = stream.stream_position()?;
 
         let offsets = loca.offsets.as_slice();
-        let offsets = offsets.windows(2).filter(|w| w[0] != w[1]);
         let mut glyphs = Vec::new();
+        let mut lengths = Vec::new();
+
+        for (glyph_id, window) in offsets.windows(2).enumerate() {
+            let [start, end] = [window[0], window[1]];
+            let offset = table_offset + start as u64;
+            let invalid = |description: String, source: Option<Error>| Error::InvalidGlyph {
+                tag: tags::GLYF,
+                offset,
+                glyph_id: glyph_id as u16,
+                description,
+                source: source.map(Box::new),
+            };
+
+            if start > end {
+                return Err(invalid(
+                    "loca offsets are not in ascending order".into(),
+                    None,
+                ));
+            }
+
+            // the last glyph is often counted with its padding in loca but not in the table length
+            if end as usize > padded_len(length as usize, ALIGNMENT) {
+                return Err(invalid("loca offset beyond glyf length".into(), None));
+            }
 
-        for _ in offsets {
-            let glyph = Glyph::try_from_stream(stream)?;
-            let position = stream.stream_position()?;
-            let offset = position - table_offset;
-            let padding = (4 - (offset % 4)) % 4;
-            let new_position = SeekFrom::Current(padding as i64);
-            stream.seek(new_position)?;
-            glyphs.push(glyph);
+            if start == end {
+                glyphs.push(Opt::None);
+                continue;
+            }
+
+            stream.seek(SeekFrom::Start(offset))?;
+            let glyph =
+                Glyph::try_from_stream(stream).map_err(|e| invalid(e.to_string(), Some(e)))?;
+            let parsed = stream.stream_position()? - offset;
+            lengths.push((parsed as usize, (end - start) as usize));
+            glyphs.push(Opt::Some(glyph));
         }
 
+        // glyphs are written back with the padding the font used, so unmodified tables keep their bytes
+        let alignment = [4, 2, 1]
+            .into_iter()
+            .find(|&a| lengths.iter().all(|&(p, l)| padded_len(p, a) == l))
+            .unwrap_or(ALIGNMENT);
+
         Ok(Self {
             glyphs: glyphs.into(),
+            alignment,
         })
     }
+
+    pub fn new(glyphs: Seq<Opt<Glyph>>) -> Self {
+        Self {
+            glyphs,
+            alignment: ALIGNMENT,
+        }
+    }
+
+    pub fn get(&self, glyph_id: u16) -> Option<&Glyph> {
+        self.glyphs.as_slice().get(glyph_id as usize)?.as_option()
+    }
+
+    pub fn resolve(&self, glyph_id: u16, maxp: &Maxp) -> Result<ResolvedGlyph, Error> {
+        GlyphResolver::new(self, maxp).resolve(glyph_id)
+    }
+
+    pub fn outline<P>(&self, glyph_id: u16, maxp: &Maxp, pen: &mut P) -> Result<(), Error>
+    where
+        P: OutlinePen,
+    {
+        self.resolve(glyph_id, maxp)?.outline(pen)
+    }
+
+    pub fn num_glyphs(&self) -> usize {
+        self.glyphs.as_slice().len()
+    }
+
+    pub fn offsets(&self) -> Result<Seq<u32>, EncodeError> {
+        let mut offset = 0;
+        let mut offsets = vec![offset];
+
+        for glyph in self.glyphs.iter() {
+            if let Opt::Some(glyph) = glyph {
+                offset += padded_len(encode_to_vec(glyph)?.len(), self.alignment) as u32;
+            }
+
+            offsets.push(offset);
+        }
+
+        Ok(offsets.into())
+    }
+}
+
+impl Encode for Glyf {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        for glyph in self.glyphs.iter() {
+            if let Opt::Some(glyph) = glyph {
+                let mut bytes = encode_to_vec(glyph)?;
+                bytes.resize(padded_len(bytes.len(), self.alignment), 0);
+                Seq::from(bytes).encode(encoder)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GlyfRef<'a> {
+    data: &'a [u8],
+    loca: LocaRef<'a>,
+}
+
+impl<'a> GlyfRef<'a> {
+    pub fn new(data: &'a [u8], loca: LocaRef<'a>) -> Self {
+        Self { data, loca }
+    }
+
+    pub fn num_glyphs(&self) -> usize {
+        self.loca.len().saturating_sub(1)
+    }
+
+    pub fn glyph_data(&self, glyph_id: u16) -> Option<&'a [u8]> {
+        self.data.get(self.loca.glyph_range(glyph_id)?)
+    }
+
+    pub fn header(&self, glyph_id: u16) -> Option<GlyphHeader> {
+        let data = self.glyph_data(glyph_id)?;
+
+        Some(GlyphHeader {
+            number_of_contours: read_be(data, 0)?,
+            x_min: read_be(data, 2)?,
+            y_min: read_be(data, 4)?,
+            x_max: read_be(data, 6)?,
+            y_max: read_be(data, 8)?,
+        })
+    }
+
+    pub fn bounds(&self, glyph_id: u16) -> Option<[i16; 4]> {
+        let header = self.header(glyph_id)?;
+        Some([header.x_min, header.y_min, header.x_max, header.y_max])
+    }
+
+    pub fn glyph(&self, glyph_id: u16) -> Result<Option<Glyph>, Error> {
+        let data = self
+            .glyph_data(glyph_id)
+            .ok_or(Error::InvalidGlyphId(glyph_id))?;
+
+        match data.is_empty() {
+            true => Ok(None),
+            false => Glyph::try_from_stream(&mut Cursor::new(data)).map(Some),
+        }
+    }
+}
+
+fn padded_len(length: usize, alignment: usize) -> usize {
+    length.div_ceil(alignment) * alignment
 }
@@ -6,7 +6,7 @@ use crate::{
         bincode::decode_from_reader,
         bitflag::BitFlag,
         reader::{ReadSeq, TryFromStream},
-        types::{Opt, Seq},
+        types::{EncodeSeq, Opt, Seq},
     },
 };
 use bincode::Encode;
@@ -14,11 +14,15 @@ use std::io::{Read, Seek};
 
 const ARGS_1_AND_2_ARE_WORDS: u16 = 0;
 const ARGS_1_AND_2_ARE_XY_VALUES: u16 = 1;
+const ROUND_XY_TO_GRID: u16 = 2;
 const WE_HAVE_SCALE: u16 = 3;
 const MORE_COMPONENTS: u16 = 5;
 const WE_HAVE_X_AND_Y_SCALE: u16 = 6;
 const WE_HAVE_A_TWO_BY_TWO: u16 = 7;
 const WE_HAVE_INSTRUCTIONS: u16 = 8;
+const USE_MY_METRICS: u16 = 9;
+const SCALED_COMPONENT_OFFSET: u16 = 11;
+const UNSCALED_COMPONENT_OFFSET: u16 = 12;
 
 #[derive(Debug, Encode)]
 pub struct CompoundGlyph {
@@ -46,9 +50,8 @@ impl TryFromStream for CompoundGlyph {
             }
         }
 
-        let has_instructions = components
-            .last()
-            .is_some_and(|l| l.flags.has(WE_HAVE_INSTRUCTIONS));
+        // instructions follow the last component if any of them, not only the last, sets the flag
+        let has_instructions = components.iter().any(|c| c.flags.has(WE_HAVE_INSTRUCTIONS));
 
         if has_instructions {
             let length = decode_from_reader(stream)?;
@@ -77,6 +80,8 @@ pub struct ComponentGlyph {
     pub y_scale: Opt<F2Dot14>,
 }
 
+impl EncodeSeq for ComponentGlyph {}
+
 impl TryFromStream for ComponentGlyph {
     fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
     where
@@ -118,6 +123,50 @@ impl TryFromStream for ComponentGlyph {
     }
 }
 
+impl ComponentGlyph {
+    pub fn args_are_xy_values(&self) -> bool {
+        self.flags.has(ARGS_1_AND_2_ARE_XY_VALUES)
+    }
+
+    pub fn round_xy_to_grid(&self) -> bool {
+        self.flags.has(ROUND_XY_TO_GRID)
+    }
+
+    pub fn more_components(&self) -> bool {
+        self.flags.has(MORE_COMPONENTS)
+    }
+
+    pub fn we_have_instructions(&self) -> bool {
+        self.flags.has(WE_HAVE_INSTRUCTIONS)
+    }
+
+    pub fn use_my_metrics(&self) -> bool {
+        self.flags.has(USE_MY_METRICS)
+    }
+
+    pub fn scaled_component_offset(&self) -> bool {
+        self.flags.has(SCALED_COMPONENT_OFFSET) && !self.flags.has(UNSCALED_COMPONENT_OFFSET)
+    }
+
+    pub fn matrix(&self) -> [f32; 4] {
+        let value = |v: &Opt<F2Dot14>| v.as_option().map(|&v| f2dot14_to_f32(v));
+
+        match value(&self.scale) {
+            Some(scale) => [scale, 0.0, 0.0, scale],
+            None => [
+                value(&self.x_scale).unwrap_or(1.0),
+                value(&self.scale_01).unwrap_or(0.0),
+                value(&self.scale_10).unwrap_or(0.0),
+                value(&self.y_scale).unwrap_or(1.0),
+            ],
+        }
+    }
+}
+
+fn f2dot14_to_f32(value: F2Dot14) -> f32 {
+    value as i16 as f32 / 16384.0
+}
+
 fn read_argument<T>(flags: u16, stream: &mut T) -> Result<Coord, Error>
 where
     T: Read,
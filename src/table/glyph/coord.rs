@@ -1,3 +1,4 @@
+use crate::utils::types::EncodeSeq;
 use bincode::{enc::Encoder, error::EncodeError, Encode};
 
 #[derive(Debug, Clone, Copy)]
@@ -18,3 +19,16 @@ impl Encode for Coord {
         }
     }
 }
+
+impl EncodeSeq for Coord {}
+
+impl Coord {
+    pub fn value(self) -> i32 {
+        match self {
+            Coord::Int8(value) => value as i32,
+            Coord::Int16(value) => value as i32,
+            Coord::UInt8(value) => value as i32,
+            Coord::UInt16(value) => value as u16 as i32,
+        }
+    }
+}
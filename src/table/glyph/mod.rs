@@ -1,10 +1,16 @@
 mod compound;
 mod coord;
+mod outline;
+mod resolve;
 mod simple;
+mod stats;
 
 pub use compound::{ComponentGlyph, CompoundGlyph};
 pub use coord::Coord;
-pub use simple::SimpleGlyph;
+pub use outline::{draw_contour, GlyphOutline, OutlinePen, OutlinePoint};
+pub use resolve::{GlyphResolver, ResolvedGlyph, MAX_COMPONENT_DEPTH};
+pub use simple::{Point, SimpleGlyph};
+pub use stats::{glyph_stats, GlyphStats};
 
 use crate::{error::Error, utils::reader::TryFromStream};
 use bincode::{enc::Encoder, error::EncodeError, Decode, Encode};
@@ -33,6 +39,25 @@ impl TryFromStream for Glyph {
     }
 }
 
+impl Glyph {
+    pub fn from_simple(glyph: SimpleGlyph) -> Self {
+        let [x_min, y_min, x_max, y_max] = glyph.bounds();
+
+        let header = GlyphHeader {
+            number_of_contours: glyph.end_pts_of_contours.as_slice().len() as i16,
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        };
+
+        Self {
+            header,
+            data: GlyphData::Simple(glyph),
+        }
+    }
+}
+
 #[derive(Debug, Encode, Decode)]
 pub struct GlyphHeader {
     pub number_of_contours: i16,
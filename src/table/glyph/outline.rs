@@ -0,0 +1,103 @@
+use crate::{
+    error::Error,
+    table::glyph::{Glyph, GlyphData, Point, SimpleGlyph},
+};
+
+pub trait OutlinePen {
+    fn move_to(&mut self, x: f32, y: f32);
+    fn line_to(&mut self, x: f32, y: f32);
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32);
+    fn close(&mut self);
+}
+
+pub trait GlyphOutline {
+    fn outline<P: OutlinePen>(&self, pen: &mut P) -> Result<(), Error>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlinePoint {
+    pub x: f32,
+    pub y: f32,
+    pub on_curve: bool,
+}
+
+impl OutlinePoint {
+    fn midpoint(self, other: Self) -> Self {
+        Self {
+            x: (self.x + other.x) / 2.0,
+            y: (self.y + other.y) / 2.0,
+            on_curve: true,
+        }
+    }
+}
+
+impl From<Point> for OutlinePoint {
+    fn from(value: Point) -> Self {
+        Self {
+            x: value.x as f32,
+            y: value.y as f32,
+            on_curve: value.on_curve,
+        }
+    }
+}
+
+impl GlyphOutline for SimpleGlyph {
+    fn outline<P: OutlinePen>(&self, pen: &mut P) -> Result<(), Error> {
+        for contour in self.contours() {
+            let points = contour
+                .into_iter()
+                .map(OutlinePoint::from)
+                .collect::<Vec<_>>();
+            draw_contour(&points, pen);
+        }
+
+        Ok(())
+    }
+}
+
+impl GlyphOutline for Glyph {
+    fn outline<P: OutlinePen>(&self, pen: &mut P) -> Result<(), Error> {
+        match &self.data {
+            GlyphData::Simple(glyph) => glyph.outline(pen),
+            GlyphData::Compound(_) => Err(Error::UnresolvedCompoundGlyph),
+        }
+    }
+}
+
+pub fn draw_contour<P: OutlinePen>(points: &[OutlinePoint], pen: &mut P) {
+    let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+        return;
+    };
+
+    // a contour made only of off-curve points starts on the implied midpoint of its ends
+    let (start, rest) = match points.iter().position(|p| p.on_curve) {
+        Some(index) => (
+            points[index],
+            [&points[index + 1..], &points[..index]].concat(),
+        ),
+        None => (last.midpoint(first), points.to_vec()),
+    };
+
+    let mut control: Option<OutlinePoint> = None;
+
+    pen.move_to(start.x, start.y);
+
+    for point in rest {
+        match (control.take(), point.on_curve) {
+            (Some(c), true) => pen.quad_to(c.x, c.y, point.x, point.y),
+            (None, true) => pen.line_to(point.x, point.y),
+            (Some(c), false) => {
+                let mid = c.midpoint(point);
+                pen.quad_to(c.x, c.y, mid.x, mid.y);
+                control = Some(point);
+            }
+            (None, false) => control = Some(point),
+        }
+    }
+
+    if let Some(c) = control {
+        pen.quad_to(c.x, c.y, start.x, start.y);
+    }
+
+    pen.close();
+}
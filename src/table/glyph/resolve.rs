@@ -0,0 +1,208 @@
+use crate::{
+    error::Error,
+    table::{
+        glyph::{draw_contour, ComponentGlyph, GlyphData, GlyphOutline, OutlinePen, OutlinePoint},
+        Glyf, Maxp,
+    },
+};
+
+pub const MAX_COMPONENT_DEPTH: u16 = 64;
+
+// shared components can expand exponentially, so the total work per glyph is capped as well
+const MAX_RESOLVED_POINTS: usize = u16::MAX as usize;
+const MAX_COMPONENT_VISITS: usize = u16::MAX as usize;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ResolvedGlyph {
+    pub contours: Vec<Vec<OutlinePoint>>,
+    pub metrics_glyph: Option<u16>,
+}
+
+impl ResolvedGlyph {
+    pub fn points(&self) -> impl Iterator<Item = &OutlinePoint> {
+        self.contours.iter().flatten()
+    }
+
+    pub fn bounds(&self) -> Option<[f32; 4]> {
+        self.points().fold(None, |bounds, p| {
+            let [x_min, y_min, x_max, y_max] = bounds.unwrap_or([p.x, p.y, p.x, p.y]);
+            Some([
+                x_min.min(p.x),
+                y_min.min(p.y),
+                x_max.max(p.x),
+                y_max.max(p.y),
+            ])
+        })
+    }
+}
+
+impl GlyphOutline for ResolvedGlyph {
+    fn outline<P: OutlinePen>(&self, pen: &mut P) -> Result<(), Error> {
+        for contour in &self.contours {
+            draw_contour(contour, pen);
+        }
+
+        Ok(())
+    }
+}
+
+pub struct GlyphResolver<'a> {
+    glyf: &'a Glyf,
+    max_depth: u16,
+}
+
+impl<'a> GlyphResolver<'a> {
+    pub fn new(glyf: &'a Glyf, maxp: &Maxp) -> Self {
+        Self {
+            glyf,
+            max_depth: maxp.max_component_depth.clamp(1, MAX_COMPONENT_DEPTH),
+        }
+    }
+
+    pub fn resolve(&self, glyph_id: u16) -> Result<ResolvedGlyph, Error> {
+        match self.resolve_at_depth(glyph_id, &mut Vec::new(), &mut 0) {
+            Err(Error::GlyphTooComplex(_)) => Err(Error::GlyphTooComplex(glyph_id)),
+            result => result,
+        }
+    }
+
+    fn resolve_at_depth(
+        &self,
+        glyph_id: u16,
+        stack: &mut Vec<u16>,
+        visits: &mut usize,
+    ) -> Result<ResolvedGlyph, Error> {
+        *visits += 1;
+
+        if *visits > MAX_COMPONENT_VISITS {
+            return Err(Error::GlyphTooComplex(glyph_id));
+        }
+
+        if stack.contains(&glyph_id) {
+            return Err(Error::ComponentCycle(glyph_id));
+        }
+
+        if stack.len() > self.max_depth as usize {
+            return Err(Error::ComponentDepthExceeded(self.max_depth));
+        }
+
+        let glyph = self
+            .glyf
+            .glyphs
+            .as_slice()
+            .get(glyph_id as usize)
+            .ok_or(Error::InvalidGlyphId(glyph_id))?;
+
+        let Some(glyph) = glyph.as_option() else {
+            return Ok(ResolvedGlyph::default());
+        };
+
+        let components = match &glyph.data {
+            GlyphData::Simple(simple) => {
+                let contours = simple
+                    .contours()
+                    .into_iter()
+                    .map(|c| c.into_iter().map(OutlinePoint::from).collect())
+                    .collect();
+
+                return Ok(ResolvedGlyph {
+                    contours,
+                    metrics_glyph: None,
+                });
+            }
+            GlyphData::Compound(compound) => compound.components.as_slice(),
+        };
+
+        let mut resolved = ResolvedGlyph::default();
+        let mut points = 0;
+
+        stack.push(glyph_id);
+
+        for component in components {
+            let child = self.resolve_at_depth(component.glyph_index, stack, visits)?;
+            points += child.points().count();
+
+            if points > MAX_RESOLVED_POINTS {
+                return Err(Error::GlyphTooComplex(glyph_id));
+            }
+
+            self.append_component(&mut resolved, component, child)?;
+        }
+
+        stack.pop();
+
+        Ok(resolved)
+    }
+
+    fn append_component(
+        &self,
+        parent: &mut ResolvedGlyph,
+        component: &ComponentGlyph,
+        child: ResolvedGlyph,
+    ) -> Result<(), Error> {
+        let [a, b, c, d] = component.matrix();
+        let transform = |p: &OutlinePoint| (a * p.x + c * p.y, b * p.x + d * p.y);
+
+        let contours = child
+            .contours
+            .iter()
+            .map(|contour| {
+                contour
+                    .iter()
+                    .map(|p| {
+                        let (x, y) = transform(p);
+                        OutlinePoint { x, y, ..*p }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let (mut dx, mut dy) = match component.args_are_xy_values() {
+            true => {
+                let offset = OutlinePoint {
+                    x: component.argument1.value() as f32,
+                    y: component.argument2.value() as f32,
+                    on_curve: true,
+                };
+
+                match component.scaled_component_offset() {
+                    true => transform(&offset),
+                    false => (offset.x, offset.y),
+                }
+            }
+            false => {
+                let parent_index = component.argument1.value() as usize;
+                let child_index = component.argument2.value() as usize;
+                let parent_point = parent.points().nth(parent_index);
+                let child_point = contours.iter().flatten().nth(child_index);
+
+                match (parent_point, child_point) {
+                    (Some(p), Some(c)) => (p.x - c.x, p.y - c.y),
+                    _ => return Err(Error::InvalidComponentPoint(component.glyph_index)),
+                }
+            }
+        };
+
+        if component.round_xy_to_grid() {
+            dx = dx.round();
+            dy = dy.round();
+        }
+
+        if component.use_my_metrics() {
+            parent.metrics_glyph = Some(component.glyph_index);
+        }
+
+        parent.contours.extend(contours.into_iter().map(|contour| {
+            contour
+                .into_iter()
+                .map(|p| OutlinePoint {
+                    x: p.x + dx,
+                    y: p.y + dy,
+                    on_curve: p.on_curve,
+                })
+                .collect()
+        }));
+
+        Ok(())
+    }
+}
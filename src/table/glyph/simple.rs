@@ -6,14 +6,25 @@ use crate::{
 use bincode::Encode;
 use std::{
     io::{Read, Seek},
-    iter::repeat,
+    iter::repeat_n,
 };
 
+const ON_CURVE_POINT: u8 = 0;
 const X_SHORT_VECTOR: u8 = 1;
 const Y_SHORT_VECTOR: u8 = 2;
 const REPEAT: u8 = 3;
 const X_SAME_OR_POSITIVE: u8 = 4;
 const Y_SAME_OR_POSITIVE: u8 = 5;
+const OVERLAP_SIMPLE: u8 = 6;
+
+const MAX_REPEAT: usize = u8::MAX as usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    pub x: i16,
+    pub y: i16,
+    pub on_curve: bool,
+}
 
 #[derive(Debug, Encode)]
 pub struct SimpleGlyph {
@@ -30,15 +41,16 @@ impl SimpleGlyph {
     where
         T: Read + Seek,
     {
-        let end_pts_of_contours = stream.read_seq(contours as usize)?;
+        let end_pts_of_contours = stream.read_seq::<u16>(contours as usize)?;
         let instruction_length = decode_from_reader(stream)?;
         let instructions = stream.read_seq(instruction_length as usize)?;
-        let last_point = end_pts_of_contours.as_slice().last().cloned();
-        let points_count = last_point.unwrap_or_default() + 1;
-        let (flags, flags_logical) = parse_outline_flags(points_count, stream)?;
-        let (x_coordinates, _) =
+        let last_point = end_pts_of_contours.as_slice().last();
+        let points_count = last_point.map_or(0, |&p| p as usize + 1);
+        let flags = parse_outline_flags(points_count, stream)?;
+        let flags_logical = expand_flags(flags.as_slice());
+        let x_coordinates =
             parse_coordinates(&flags_logical, stream, X_SHORT_VECTOR, X_SAME_OR_POSITIVE)?;
-        let (y_coordinates, _) =
+        let y_coordinates =
             parse_coordinates(&flags_logical, stream, Y_SHORT_VECTOR, Y_SAME_OR_POSITIVE)?;
 
         Ok(Self {
@@ -50,27 +62,126 @@ impl SimpleGlyph {
             y_coordinates,
         })
     }
+
+    pub fn from_contours(contours: &[Vec<Point>], instructions: Vec<u8>) -> Self {
+        let mut end_pts_of_contours = Vec::new();
+        let mut flags_logical = Vec::new();
+        let mut x_coordinates = Vec::new();
+        let mut y_coordinates = Vec::new();
+        let mut last = (0, 0);
+
+        // an empty contour has no end point to record, so it is left out
+        for contour in contours.iter().filter(|c| !c.is_empty()) {
+            for point in contour {
+                let dx = point.x.wrapping_sub(last.0);
+                let dy = point.y.wrapping_sub(last.1);
+                let mut flag = point.on_curve as u8;
+
+                flag |= pack_delta(dx, &mut x_coordinates, X_SHORT_VECTOR, X_SAME_OR_POSITIVE);
+                flag |= pack_delta(dy, &mut y_coordinates, Y_SHORT_VECTOR, Y_SAME_OR_POSITIVE);
+                flags_logical.push(flag);
+                last = (point.x, point.y);
+            }
+
+            end_pts_of_contours.push((flags_logical.len() - 1) as u16);
+        }
+
+        Self {
+            end_pts_of_contours: end_pts_of_contours.into(),
+            instruction_length: instructions.len() as u16,
+            instructions: instructions.into(),
+            flags: compress_flags(&flags_logical).into(),
+            x_coordinates: x_coordinates.into(),
+            y_coordinates: y_coordinates.into(),
+        }
+    }
+
+    pub fn points(&self) -> Vec<Point> {
+        let flags = expand_flags(self.flags.as_slice());
+        let x_coordinates = self.x_coordinates.as_slice();
+        let y_coordinates = self.y_coordinates.as_slice();
+        let xs = unpack_deltas(&flags, x_coordinates, X_SHORT_VECTOR, X_SAME_OR_POSITIVE);
+        let ys = unpack_deltas(&flags, y_coordinates, Y_SHORT_VECTOR, Y_SAME_OR_POSITIVE);
+        let mut x = 0i16;
+        let mut y = 0i16;
+
+        flags
+            .iter()
+            .zip(xs.zip(ys))
+            .map(|(flag, (dx, dy))| {
+                x = x.wrapping_add(dx);
+                y = y.wrapping_add(dy);
+
+                Point {
+                    x,
+                    y,
+                    on_curve: flag.has(ON_CURVE_POINT),
+                }
+            })
+            .collect()
+    }
+
+    pub fn bounds(&self) -> [i16; 4] {
+        let points = self.points();
+        let x_min = points.iter().map(|p| p.x).min().unwrap_or_default();
+        let y_min = points.iter().map(|p| p.y).min().unwrap_or_default();
+        let x_max = points.iter().map(|p| p.x).max().unwrap_or_default();
+        let y_max = points.iter().map(|p| p.y).max().unwrap_or_default();
+        [x_min, y_min, x_max, y_max]
+    }
+
+    pub fn overlap_simple(&self) -> bool {
+        self.flags
+            .as_slice()
+            .first()
+            .is_some_and(|f| f.has(OVERLAP_SIMPLE))
+    }
+
+    pub fn set_overlap_simple(&mut self, overlap: bool) {
+        let mut flags = std::mem::take(&mut self.flags).into_vec();
+
+        if let Some(flag) = flags.first_mut() {
+            match overlap {
+                true => *flag |= 1 << OVERLAP_SIMPLE,
+                false => *flag &= !(1 << OVERLAP_SIMPLE),
+            }
+        }
+
+        self.flags = flags.into();
+    }
+
+    pub fn contours(&self) -> Vec<Vec<Point>> {
+        let points = self.points();
+        let mut start = 0;
+        let mut contours = Vec::new();
+
+        for &end in self.end_pts_of_contours.iter() {
+            let end = (end as usize + 1).min(points.len()).max(start);
+            contours.push(points[start..end].to_vec());
+            start = end;
+        }
+
+        contours
+    }
 }
 
-fn parse_outline_flags<T>(points: u16, stream: &mut T) -> Result<(Seq<u8>, Vec<u8>), Error>
+fn parse_outline_flags<T>(points: usize, stream: &mut T) -> Result<Seq<u8>, Error>
 where
     T: Read,
 {
     let mut i = 0;
     let mut last_flag: Option<u8> = None;
     let mut flags = Vec::<u8>::new();
-    let mut flags_logical = Vec::<u8>::new();
+    let mut value = [0u8];
 
     while i < points {
-        let value = decode_from_reader(stream)?;
+        stream.read_exact(&mut value)?;
+        let [value] = value;
         let repeated_flag = last_flag.take().filter(|l| l.has(REPEAT));
 
-        if let Some(flag) = repeated_flag {
-            let repeated = repeat(flag).take(value as usize);
-            flags_logical.extend(repeated);
-            i += value as u16;
+        if repeated_flag.is_some() {
+            i += value as usize;
         } else {
-            flags_logical.push(value);
             last_flag = Some(value);
             i += 1;
         }
@@ -78,7 +189,48 @@ where
         flags.push(value);
     }
 
-    Ok((flags.into(), flags_logical))
+    Ok(flags.into())
+}
+
+fn expand_flags(flags: &[u8]) -> Vec<u8> {
+    let mut last_flag: Option<u8> = None;
+    let mut flags_logical = Vec::<u8>::new();
+
+    for &value in flags {
+        let repeated_flag = last_flag.take().filter(|l| l.has(REPEAT));
+
+        if let Some(flag) = repeated_flag {
+            flags_logical.extend(repeat_n(flag, value as usize));
+        } else {
+            flags_logical.push(value);
+            last_flag = Some(value);
+        }
+    }
+
+    flags_logical
+}
+
+fn compress_flags(flags: &[u8]) -> Vec<u8> {
+    let mut i = 0;
+    let mut compressed = Vec::new();
+
+    while i < flags.len() {
+        let flag = flags[i];
+        let run = flags[i..].iter().take_while(|&&f| f == flag).count();
+        let repeats = (run - 1).min(MAX_REPEAT);
+
+        // a repeat costs two bytes, so it only pays off from three identical flags
+        if repeats >= 2 {
+            compressed.push(flag | 1 << REPEAT);
+            compressed.push(repeats as u8);
+            i += repeats + 1;
+        } else {
+            compressed.push(flag);
+            i += 1;
+        }
+    }
+
+    compressed
 }
 
 fn parse_coordinates<T>(
@@ -86,35 +238,70 @@ fn parse_coordinates<T>(
     stream: &mut T,
     size_flag: u8,
     sign_flag: u8,
-) -> Result<(Seq<Coord>, Vec<Coord>), Error>
+) -> Result<Seq<Coord>, Error>
 where
     T: Read,
 {
-    let mut i = 0;
-    let mut coordinates = Vec::<Coord>::new();
-    let mut coordinates_logical = Vec::<Coord>::new();
+    let sizes = flags
+        .iter()
+        .map(|flag| match (flag.get(size_flag), flag.get(sign_flag)) {
+            (1, _) => 1,
+            (0, 0) => 2,
+            _ => 0,
+        })
+        .collect::<Vec<usize>>();
+    let bytes = stream.read_seq::<u8>(sizes.iter().sum())?.into_vec();
+    let mut rest = bytes.as_slice();
+    let mut coordinates = Vec::<Coord>::with_capacity(sizes.len());
 
-    while i < flags.len() {
-        let flag = flags[i];
+    for size in sizes {
+        match (size, rest) {
+            (1, [value, tail @ ..]) => {
+                coordinates.push(Coord::UInt8(*value));
+                rest = tail;
+            }
+            (2, [high, low, tail @ ..]) => {
+                coordinates.push(Coord::Int16(i16::from_be_bytes([*high, *low])));
+                rest = tail;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(coordinates.into())
+}
+
+fn unpack_deltas<'a>(
+    flags: &'a [u8],
+    coordinates: &'a [Coord],
+    size_flag: u8,
+    sign_flag: u8,
+) -> impl Iterator<Item = i16> + 'a {
+    let mut coordinates = coordinates.iter();
+
+    flags.iter().map(move |flag| {
         let size_flag = flag.get(size_flag);
         let sign_flag = flag.get(sign_flag);
 
-        let coord = match (size_flag, sign_flag) {
-            (1, _) => Some(Coord::UInt8(decode_from_reader(stream)?)),
-            (0, 0) => Some(Coord::Int16(decode_from_reader(stream)?)),
-            _ => None,
-        };
-
-        if let Some(coord) = coord {
-            coordinates.push(coord);
-            coordinates_logical.push(coord);
-        } else {
-            let last = coordinates.last().cloned().unwrap_or(Coord::UInt16(0));
-            coordinates_logical.push(last);
+        match (size_flag, sign_flag) {
+            (1, 1) => coordinates.next().map_or(0, |c| c.value() as i16),
+            (1, _) => coordinates.next().map_or(0, |c| -(c.value() as i16)),
+            (0, 0) => coordinates.next().map_or(0, |c| c.value() as i16),
+            _ => 0,
         }
+    })
+}
 
-        i += 1;
+fn pack_delta(delta: i16, coordinates: &mut Vec<Coord>, size_flag: u8, sign_flag: u8) -> u8 {
+    match delta {
+        0 => 1 << sign_flag,
+        -255..=255 => {
+            coordinates.push(Coord::UInt8(delta.unsigned_abs() as u8));
+            1 << size_flag | ((delta > 0) as u8) << sign_flag
+        }
+        _ => {
+            coordinates.push(Coord::Int16(delta));
+            0
+        }
     }
-
-    Ok((coordinates.into(), coordinates_logical))
 }
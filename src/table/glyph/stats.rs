@@ -0,0 +1,72 @@
+use crate::{
+    error::Error,
+    table::{
+        glyph::{GlyphData, MAX_COMPONENT_DEPTH},
+        Glyf,
+    },
+};
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GlyphStats {
+    pub points: u32,
+    pub contours: u32,
+    pub depth: u16,
+}
+
+pub fn glyph_stats(
+    glyf: &Glyf,
+    glyph_id: u16,
+    memo: &mut [Option<GlyphStats>],
+    stack: &mut Vec<u16>,
+) -> Result<GlyphStats, Error> {
+    if let Some(stats) = memo.get(glyph_id as usize).copied().flatten() {
+        return Ok(stats);
+    }
+
+    if stack.contains(&glyph_id) {
+        return Err(Error::ComponentCycle(glyph_id));
+    }
+
+    if stack.len() >= MAX_COMPONENT_DEPTH as usize {
+        return Err(Error::ComponentDepthExceeded(MAX_COMPONENT_DEPTH));
+    }
+
+    let glyph = glyf
+        .glyphs
+        .as_slice()
+        .get(glyph_id as usize)
+        .ok_or(Error::InvalidGlyphId(glyph_id))?;
+
+    let stats = match glyph.as_option().map(|g| &g.data) {
+        None => GlyphStats::default(),
+        Some(GlyphData::Simple(simple)) => {
+            let end_pts = simple.end_pts_of_contours.as_slice();
+
+            GlyphStats {
+                points: end_pts.last().map_or(0, |&p| p as u32 + 1),
+                contours: end_pts.len() as u32,
+                depth: 0,
+            }
+        }
+        Some(GlyphData::Compound(compound)) => {
+            let mut stats = GlyphStats::default();
+            stack.push(glyph_id);
+
+            for component in compound.components.iter() {
+                let child = glyph_stats(glyf, component.glyph_index, memo, stack)?;
+                stats.points = stats.points.saturating_add(child.points);
+                stats.contours = stats.contours.saturating_add(child.contours);
+                stats.depth = stats.depth.max(child.depth + 1);
+            }
+
+            stack.pop();
+            stats
+        }
+    };
+
+    if let Some(slot) = memo.get_mut(glyph_id as usize) {
+        *slot = Some(stats);
+    }
+
+    Ok(stats)
+}
@@ -1,7 +1,7 @@
 use crate::sfnt::types::{FWord, Fixed, LongDateTime};
 use bincode::{Decode, Encode};
 
-#[derive(Debug, Encode, Decode)]
+#[derive(Debug, Clone, Encode, Decode)]
 pub struct Head {
     pub version: Fixed,
     pub font_revision: Fixed,
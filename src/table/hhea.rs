@@ -4,7 +4,7 @@ use crate::{
 };
 use bincode::{Decode, Encode};
 
-#[derive(Debug, Encode, Decode)]
+#[derive(Debug, Clone, Encode, Decode)]
 pub struct Hhea {
     pub version: Fixed,
     pub ascent: FWord,
@@ -1,8 +1,12 @@
 use crate::{
     error::Error,
     sfnt::types::FWord,
-    table::{tags::Tag, FontTable, GetFontTable},
-    utils::{reader::ReadSeq, types::Seq},
+    table::{
+        long_metric::{find_metric, read_metric, read_metrics, split_metrics, LongMetric},
+        tags::{self, Tag},
+        FontTable, GetFontTable,
+    },
+    utils::types::Seq,
 };
 use bincode::{Decode, Encode};
 use std::{
@@ -18,30 +22,98 @@ pub struct Hmtx {
 
 impl Hmtx {
     pub fn try_from_params<T>(
+        length: u32,
         tables: &BTreeMap<Tag, FontTable>,
         stream: &mut T,
     ) -> Result<Self, Error>
     where
         T: Read + Seek,
     {
-        let maxp = tables.maxp()?;
-        let hhea = tables.hhea()?;
-
-        let num_glyphs = maxp.num_glyphs as usize;
-        let num_of_long_hor_metrics = hhea.num_of_long_hor_metrics as usize;
-        let h_metrics = stream.read_seq(num_of_long_hor_metrics)?;
-        let remainder = num_glyphs - num_of_long_hor_metrics;
-        let left_side_bearing = stream.read_seq(remainder)?;
+        let num_glyphs = tables.maxp()?.num_glyphs as usize;
+        let num_of_long_hor_metrics = tables.hhea()?.num_of_long_hor_metrics as usize;
+        let (h_metrics, left_side_bearing) = read_metrics(
+            tags::HMTX,
+            "hhea.numberOfHMetrics",
+            length,
+            num_of_long_hor_metrics,
+            num_glyphs,
+            stream,
+        )?;
 
         Ok(Self {
             h_metrics,
             left_side_bearing,
         })
     }
+
+    pub fn from_metrics(metrics: &[LongHorMetric]) -> Self {
+        let (h_metrics, left_side_bearing) = split_metrics(metrics);
+
+        Self {
+            h_metrics,
+            left_side_bearing,
+        }
+    }
+
+    pub fn metric(&self, glyph_id: u16) -> Option<LongHorMetric> {
+        let left_side_bearing = self.left_side_bearing.as_slice();
+        find_metric(self.h_metrics.as_slice(), left_side_bearing, glyph_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HmtxRef<'a> {
+    data: &'a [u8],
+    num_of_long_hor_metrics: usize,
+    num_glyphs: usize,
 }
 
-#[derive(Debug, Encode, Decode)]
+impl<'a> HmtxRef<'a> {
+    pub fn new(data: &'a [u8], num_of_long_hor_metrics: usize, num_glyphs: usize) -> Self {
+        Self {
+            data,
+            num_of_long_hor_metrics,
+            num_glyphs,
+        }
+    }
+
+    pub fn metric(&self, glyph_id: u16) -> Option<LongHorMetric> {
+        read_metric(
+            self.data,
+            self.num_of_long_hor_metrics,
+            self.num_glyphs,
+            glyph_id,
+        )
+    }
+
+    pub fn advance_width(&self, glyph_id: u16) -> Option<u16> {
+        self.metric(glyph_id).map(|m| m.advance_width)
+    }
+
+    pub fn left_side_bearing(&self, glyph_id: u16) -> Option<i16> {
+        self.metric(glyph_id).map(|m| m.left_side_bearing)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode)]
 pub struct LongHorMetric {
     pub advance_width: u16,
     pub left_side_bearing: i16,
 }
+
+impl LongMetric for LongHorMetric {
+    fn new(advance_width: u16, left_side_bearing: i16) -> Self {
+        Self {
+            advance_width,
+            left_side_bearing,
+        }
+    }
+
+    fn advance(&self) -> u16 {
+        self.advance_width
+    }
+
+    fn side_bearing(&self) -> i16 {
+        self.left_side_bearing
+    }
+}
@@ -1,10 +1,19 @@
 use crate::{
     error::Error,
-    table::{tags::Tag, FontTable, GetFontTable},
-    utils::{reader::ReadSeq, types::Seq},
+    table::{
+        tags::{self, Tag},
+        FontTable, GetFontTable,
+    },
+    utils::{bytes::read_be, reader::ReadSeq, types::Seq},
 };
 use bincode::{enc::Encoder, error::EncodeError, Encode};
-use std::{collections::BTreeMap, io::Read};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek},
+    ops::Range,
+};
+
+const MAX_SHORT_OFFSET: u32 = u16::MAX as u32 * 2;
 
 #[derive(Debug)]
 pub struct Loca {
@@ -14,30 +23,41 @@ pub struct Loca {
 
 impl Loca {
     pub fn try_from_params<T>(
+        length: u32,
         tables: &BTreeMap<Tag, FontTable>,
         stream: &mut T,
     ) -> Result<Self, Error>
     where
-        T: Read,
+        T: Read + Seek,
     {
+        let table_offset = stream.stream_position()?;
         let head = tables.head()?;
         let maxp = tables.maxp()?;
 
         let loc_format = head.index_to_loc_format;
-        let length = maxp.num_glyphs as usize + 1;
+        let count = maxp.num_glyphs as usize + 1;
 
         let format = match loc_format {
             0 => LocaFormat::Short,
             _ => LocaFormat::Long,
         };
 
+        if (length as usize) < format.size() * count {
+            return Err(Error::InvalidTable {
+                tag: tags::LOCA,
+                offset: table_offset,
+                description: "loca table is shorter than maxp.numGlyphs + 1 offsets".into(),
+                source: None,
+            });
+        }
+
         let offsets = match format {
             LocaFormat::Short => stream
-                .read_seq::<u16>(length)?
+                .read_seq::<u16>(count)?
                 .into_iter()
                 .map(|o| o as u32 * 2)
                 .collect(),
-            LocaFormat::Long => stream.read_seq(length)?,
+            LocaFormat::Long => stream.read_seq(count)?,
         };
 
         Ok(Self { offsets, format })
@@ -46,6 +66,12 @@ impl Loca {
 
 impl Encode for Loca {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        if !self.format.fits(self.offsets.as_slice()) {
+            return Err(EncodeError::Other(
+                "loca offsets do not fit the short format",
+            ));
+        }
+
         match self.format {
             LocaFormat::Short => self
                 .offsets
@@ -62,3 +88,66 @@ pub enum LocaFormat {
     Short,
     Long,
 }
+
+impl LocaFormat {
+    pub fn size(self) -> usize {
+        match self {
+            LocaFormat::Short => 2,
+            LocaFormat::Long => 4,
+        }
+    }
+
+    // short offsets are stored halved, so they must be even and at most 0x1FFFE
+    pub fn fits(self, offsets: &[u32]) -> bool {
+        match self {
+            LocaFormat::Short => offsets.iter().all(|&o| o % 2 == 0 && o <= MAX_SHORT_OFFSET),
+            LocaFormat::Long => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LocaRef<'a> {
+    data: &'a [u8],
+    format: LocaFormat,
+}
+
+impl<'a> LocaRef<'a> {
+    pub fn new(data: &'a [u8], format: LocaFormat, num_glyphs: usize) -> Self {
+        let length = (num_glyphs + 1) * format.size();
+
+        Self {
+            data: &data[..length.min(data.len())],
+            format,
+        }
+    }
+
+    pub fn format(&self) -> LocaFormat {
+        self.format
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() / self.format.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn offset(&self, index: usize) -> Option<u32> {
+        match self.format {
+            LocaFormat::Short => read_be::<u16>(self.data, index * 2).map(|o| o as u32 * 2),
+            LocaFormat::Long => read_be(self.data, index * 4),
+        }
+    }
+
+    pub fn glyph_range(&self, glyph_id: u16) -> Option<Range<usize>> {
+        let start = self.offset(glyph_id as usize)? as usize;
+        let end = self.offset(glyph_id as usize + 1)? as usize;
+
+        match start <= end {
+            true => Some(start..end),
+            false => None,
+        }
+    }
+}
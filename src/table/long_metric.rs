@@ -0,0 +1,130 @@
+use crate::{
+    error::Error,
+    sfnt::types::FWord,
+    table::tags::{tag_to_string, Tag},
+    utils::{
+        bytes::{read_be, write_be, FixedSize},
+        reader::ReadSeq,
+        types::Seq,
+    },
+};
+use std::io::{Read, Seek};
+
+// hmtx and vmtx share one layout: long metrics, then side bearings that reuse the last advance
+pub trait LongMetric: Copy {
+    fn new(advance: u16, side_bearing: i16) -> Self;
+
+    fn advance(&self) -> u16;
+
+    fn side_bearing(&self) -> i16;
+}
+
+impl<M> FixedSize for M
+where
+    M: LongMetric,
+{
+    const FIXED_SIZE: usize = 4;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self::new(read_be(bytes, 0)?, read_be(bytes, 2)?))
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.advance());
+        write_be(bytes, 2, &self.side_bearing());
+    }
+}
+
+pub fn read_metrics<M, T>(
+    tag: Tag,
+    count_field: &str,
+    length: u32,
+    num_long_metrics: usize,
+    num_glyphs: usize,
+    stream: &mut T,
+) -> Result<(Seq<M>, Seq<FWord>), Error>
+where
+    M: LongMetric,
+    T: Read + Seek,
+{
+    let table_offset = stream.stream_position()?;
+    let invalid = |description: String| Error::InvalidTable {
+        tag,
+        offset: table_offset,
+        description,
+        source: None,
+    };
+
+    let Some(remainder) = num_glyphs.checked_sub(num_long_metrics) else {
+        return Err(invalid(format!("{count_field} exceeds maxp.numGlyphs")));
+    };
+
+    if (length as usize) < num_long_metrics * 4 + remainder * 2 {
+        let name = tag_to_string(tag);
+        return Err(invalid(format!(
+            "{name} table is shorter than its metrics require"
+        )));
+    }
+
+    let long_metrics = stream.read_seq(num_long_metrics)?;
+    let side_bearings = stream.read_seq(remainder)?;
+
+    Ok((long_metrics, side_bearings))
+}
+
+pub fn split_metrics<M>(metrics: &[M]) -> (Seq<M>, Seq<FWord>)
+where
+    M: LongMetric,
+{
+    let last_advance = metrics.last().map(M::advance);
+    let trailing = metrics
+        .iter()
+        .rev()
+        .take_while(|m| Some(m.advance()) == last_advance)
+        .count();
+    let long_metrics = (metrics.len() - trailing + 1).min(metrics.len());
+    let (long_metrics, short_metrics) = metrics.split_at(long_metrics);
+    let side_bearings = short_metrics.iter().map(M::side_bearing).collect();
+
+    (long_metrics.to_vec().into(), side_bearings)
+}
+
+pub fn find_metric<M>(long_metrics: &[M], side_bearings: &[FWord], glyph_id: u16) -> Option<M>
+where
+    M: LongMetric,
+{
+    let glyph_id = glyph_id as usize;
+
+    match long_metrics.get(glyph_id) {
+        Some(&metric) => Some(metric),
+        None => Some(M::new(
+            long_metrics.last()?.advance(),
+            *side_bearings.get(glyph_id - long_metrics.len())?,
+        )),
+    }
+}
+
+pub fn read_metric<M>(
+    data: &[u8],
+    num_long_metrics: usize,
+    num_glyphs: usize,
+    glyph_id: u16,
+) -> Option<M>
+where
+    M: LongMetric,
+{
+    let glyph_id = glyph_id as usize;
+    let num_long = num_long_metrics;
+
+    if glyph_id >= num_glyphs {
+        return None;
+    }
+
+    match glyph_id.checked_sub(num_long) {
+        None => M::from_be_slice(data.get(glyph_id * 4..)?),
+        Some(index) => Some(M::new(
+            read_be(data, num_long.checked_sub(1)? * 4)?,
+            read_be(data, num_long * 4 + index * 2)?,
+        )),
+    }
+}
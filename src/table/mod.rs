@@ -4,19 +4,49 @@ mod head;
 mod hhea;
 mod hmtx;
 mod loca;
+mod long_metric;
 mod maxp;
+mod name;
+mod os2;
+mod post;
+mod vhea;
+mod vmtx;
 
 pub mod glyph;
 pub mod tags;
 
-pub use {cmap::Cmap, glyf::Glyf, head::Head, hhea::Hhea, hmtx::Hmtx, loca::Loca, maxp::Maxp};
+pub use {
+    cmap::{
+        Cmap, CmapHeader, CmapRef, CmapSubtable, CmapSubtableRef, DefaultUvs, EncodingSubtable,
+        Format0, Format10, Format12, Format12Group, Format13, Format13Group, Format14, Format2,
+        Format2SubHeader, Format4, Format6, Format8, NonDefaultUvs, UnicodeRange, UvsMapping,
+        VariantGlyph, VariationSelector,
+    },
+    glyf::{Glyf, GlyfRef},
+    head::Head,
+    hhea::Hhea,
+    hmtx::{Hmtx, HmtxRef, LongHorMetric},
+    loca::{Loca, LocaFormat, LocaRef},
+    maxp::Maxp,
+    name::{
+        LangTagRecord, Name, NameId, NameRecord, PLATFORM_MACINTOSH, PLATFORM_UNICODE,
+        PLATFORM_WINDOWS,
+    },
+    os2::{
+        EmbeddingPermission, FsSelection, FsType, Os2, Os2CodePageRange, Os2ExtendedMetrics,
+        Os2Header, Os2OpticalSize,
+    },
+    post::{PascalString, Post, PostGlyphNames, PostHeader, PostV2, PostV25, MAC_GLYPH_NAMES},
+    vhea::Vhea,
+    vmtx::{LongVerMetric, Vmtx, VmtxRef},
+};
 
 use crate::{
     error::Error,
     table::tags::Tag,
     ttf::font_dir::TableDirEntry,
     utils::{
-        reader::{ReadSeq, TryFromStream},
+        reader::{BoundedStream, ReadSeq, TryFromStream},
         types::Seq,
     },
 };
@@ -35,6 +65,11 @@ pub enum FontTable {
     Cmap(Cmap),
     Loca(Loca),
     Glyf(Glyf),
+    Name(Name),
+    Post(Post),
+    Os2(Os2),
+    Vhea(Vhea),
+    Vmtx(Vmtx),
     Other(Seq<u8>),
 }
 
@@ -48,12 +83,35 @@ impl Encode for FontTable {
             FontTable::Cmap(cmap) => cmap.encode(encoder),
             FontTable::Loca(loca) => loca.encode(encoder),
             FontTable::Glyf(glyf) => glyf.encode(encoder),
+            FontTable::Name(name) => name.encode(encoder),
+            FontTable::Post(post) => post.encode(encoder),
+            FontTable::Os2(os2) => os2.encode(encoder),
+            FontTable::Vhea(vhea) => vhea.encode(encoder),
+            FontTable::Vmtx(vmtx) => vmtx.encode(encoder),
             FontTable::Other(table) => table.encode(encoder),
         }
     }
 }
 
 impl FontTable {
+    pub fn tag(&self) -> Option<Tag> {
+        match self {
+            FontTable::Head(_) => Some(tags::HEAD),
+            FontTable::Hhea(_) => Some(tags::HHEA),
+            FontTable::Maxp(_) => Some(tags::MAXP),
+            FontTable::Hmtx(_) => Some(tags::HMTX),
+            FontTable::Cmap(_) => Some(tags::CMAP),
+            FontTable::Loca(_) => Some(tags::LOCA),
+            FontTable::Glyf(_) => Some(tags::GLYF),
+            FontTable::Name(_) => Some(tags::NAME),
+            FontTable::Post(_) => Some(tags::POST),
+            FontTable::Os2(_) => Some(tags::OS_2),
+            FontTable::Vhea(_) => Some(tags::VHEA),
+            FontTable::Vmtx(_) => Some(tags::VMTX),
+            FontTable::Other(_) => None,
+        }
+    }
+
     pub fn try_from_params<T>(
         entry: &TableDirEntry,
         tables: &BTreeMap<Tag, FontTable>,
@@ -67,15 +125,40 @@ impl FontTable {
 
         stream.seek(pos)?;
 
+        // reads stop at the table's padded end so a corrupt count cannot run into later tables
+        let end = offset + u64::from(entry.length).div_ceil(4) * 4;
+        let mut stream = BoundedStream::new(stream, end)?;
+
+        Self::from_entry(entry, tables, &mut stream).map_err(|e| e.in_table(entry.tag, offset))
+    }
+
+    fn from_entry<T>(
+        entry: &TableDirEntry,
+        tables: &BTreeMap<Tag, FontTable>,
+        stream: &mut T,
+    ) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let length = entry.length;
+
         match entry.tag {
             tags::HEAD => Ok(Self::Head(Head::try_from_stream(stream)?)),
             tags::HHEA => Ok(Self::Hhea(Hhea::try_from_stream(stream)?)),
             tags::MAXP => Ok(Self::Maxp(Maxp::try_from_stream(stream)?)),
             tags::CMAP => Ok(Self::Cmap(Cmap::try_from_stream(stream)?)),
-            tags::LOCA => Ok(Self::Loca(Loca::try_from_params(tables, stream)?)),
-            tags::HMTX => Ok(Self::Hmtx(Hmtx::try_from_params(tables, stream)?)),
-            tags::GLYF => Ok(Self::Glyf(Glyf::try_from_params(tables, stream)?)),
-            _ => Ok(stream.read_seq(entry.length as usize).map(Self::Other)?),
+            tags::LOCA => Ok(Self::Loca(Loca::try_from_params(length, tables, stream)?)),
+            tags::HMTX => Ok(Self::Hmtx(Hmtx::try_from_params(length, tables, stream)?)),
+            tags::GLYF => Ok(Self::Glyf(Glyf::try_from_params(length, tables, stream)?)),
+            tags::NAME => Ok(Self::Name(Name::try_from_stream(stream)?)),
+            tags::OS_2 => Ok(Self::Os2(Os2::try_from_stream(stream)?)),
+            tags::POST => Ok(Self::Post(Post::try_from_params(length, stream)?)),
+            tags::VHEA => Ok(Self::Vhea(Vhea::try_from_stream(stream)?)),
+            // vertical metrics are optional, without a header they are kept as raw bytes
+            tags::VMTX if tables.contains_key(&tags::VHEA) => {
+                Ok(Self::Vmtx(Vmtx::try_from_params(length, tables, stream)?))
+            }
+            _ => Ok(stream.read_seq(length as usize).map(Self::Other)?),
         }
     }
 }
@@ -88,55 +171,95 @@ pub trait GetFontTable {
     fn cmap(&self) -> Result<&Cmap, Error>;
     fn loca(&self) -> Result<&Loca, Error>;
     fn glyf(&self) -> Result<&Glyf, Error>;
+    fn name(&self) -> Result<&Name, Error>;
+    fn post(&self) -> Result<&Post, Error>;
+    fn os2(&self) -> Result<&Os2, Error>;
+    fn vhea(&self) -> Result<&Vhea, Error>;
+    fn vmtx(&self) -> Result<&Vmtx, Error>;
 }
 
 impl GetFontTable for BTreeMap<Tag, FontTable> {
     fn head(&self) -> Result<&Head, Error> {
-        match &self[&tags::HEAD] {
-            FontTable::Head(value) => Ok(value),
+        match self.get(&tags::HEAD) {
+            Some(FontTable::Head(value)) => Ok(value),
             _ => Err(Error::ExpectedTable("head")),
         }
     }
 
     fn hhea(&self) -> Result<&Hhea, Error> {
-        match &self[&tags::HHEA] {
-            FontTable::Hhea(value) => Ok(value),
+        match self.get(&tags::HHEA) {
+            Some(FontTable::Hhea(value)) => Ok(value),
             _ => Err(Error::ExpectedTable("hhea")),
         }
     }
 
     fn maxp(&self) -> Result<&Maxp, Error> {
-        match &self[&tags::MAXP] {
-            FontTable::Maxp(value) => Ok(value),
+        match self.get(&tags::MAXP) {
+            Some(FontTable::Maxp(value)) => Ok(value),
             _ => Err(Error::ExpectedTable("maxp")),
         }
     }
 
     fn hmtx(&self) -> Result<&Hmtx, Error> {
-        match &self[&tags::HMTX] {
-            FontTable::Hmtx(value) => Ok(value),
+        match self.get(&tags::HMTX) {
+            Some(FontTable::Hmtx(value)) => Ok(value),
             _ => Err(Error::ExpectedTable("hmtx")),
         }
     }
 
     fn cmap(&self) -> Result<&Cmap, Error> {
-        match &self[&tags::CMAP] {
-            FontTable::Cmap(value) => Ok(value),
+        match self.get(&tags::CMAP) {
+            Some(FontTable::Cmap(value)) => Ok(value),
             _ => Err(Error::ExpectedTable("cmap")),
         }
     }
 
     fn loca(&self) -> Result<&Loca, Error> {
-        match &self[&tags::LOCA] {
-            FontTable::Loca(value) => Ok(value),
+        match self.get(&tags::LOCA) {
+            Some(FontTable::Loca(value)) => Ok(value),
             _ => Err(Error::ExpectedTable("loca")),
         }
     }
 
     fn glyf(&self) -> Result<&Glyf, Error> {
-        match &self[&tags::GLYF] {
-            FontTable::Glyf(value) => Ok(value),
+        match self.get(&tags::GLYF) {
+            Some(FontTable::Glyf(value)) => Ok(value),
             _ => Err(Error::ExpectedTable("glyf")),
         }
     }
+
+    fn name(&self) -> Result<&Name, Error> {
+        match self.get(&tags::NAME) {
+            Some(FontTable::Name(value)) => Ok(value),
+            _ => Err(Error::ExpectedTable("name")),
+        }
+    }
+
+    fn post(&self) -> Result<&Post, Error> {
+        match self.get(&tags::POST) {
+            Some(FontTable::Post(value)) => Ok(value),
+            _ => Err(Error::ExpectedTable("post")),
+        }
+    }
+
+    fn os2(&self) -> Result<&Os2, Error> {
+        match self.get(&tags::OS_2) {
+            Some(FontTable::Os2(value)) => Ok(value),
+            _ => Err(Error::ExpectedTable("OS/2")),
+        }
+    }
+
+    fn vhea(&self) -> Result<&Vhea, Error> {
+        match self.get(&tags::VHEA) {
+            Some(FontTable::Vhea(value)) => Ok(value),
+            _ => Err(Error::ExpectedTable("vhea")),
+        }
+    }
+
+    fn vmtx(&self) -> Result<&Vmtx, Error> {
+        match self.get(&tags::VMTX) {
+            Some(FontTable::Vmtx(value)) => Ok(value),
+            _ => Err(Error::ExpectedTable("vmtx")),
+        }
+    }
 }
@@ -0,0 +1,324 @@
+use crate::{
+    error::Error,
+    utils::{
+        bincode::decode_from_reader,
+        encoding::{decode_mac_roman, decode_utf16_be},
+        reader::{ReadSeq, TryFromStream},
+        types::Seq,
+    },
+};
+use bincode::{enc::Encoder, error::EncodeError, Encode};
+use std::io::{Read, Seek, SeekFrom};
+
+const NAME_RECORD_SIZE: usize = 12;
+const LANG_TAG_RECORD_SIZE: usize = 4;
+
+pub const PLATFORM_UNICODE: u16 = 0;
+pub const PLATFORM_MACINTOSH: u16 = 1;
+pub const PLATFORM_WINDOWS: u16 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameId {
+    Copyright = 0,
+    FamilyName = 1,
+    SubfamilyName = 2,
+    UniqueId = 3,
+    FullName = 4,
+    Version = 5,
+    PostScriptName = 6,
+    Trademark = 7,
+    Manufacturer = 8,
+    Designer = 9,
+    Description = 10,
+    VendorUrl = 11,
+    DesignerUrl = 12,
+    License = 13,
+    LicenseUrl = 14,
+    TypographicFamilyName = 16,
+    TypographicSubfamilyName = 17,
+    CompatibleFullName = 18,
+    SampleText = 19,
+    PostScriptCidName = 20,
+    WwsFamilyName = 21,
+    WwsSubfamilyName = 22,
+    LightBackgroundPalette = 23,
+    DarkBackgroundPalette = 24,
+    VariationsPostScriptNamePrefix = 25,
+}
+
+#[derive(Debug)]
+pub struct Name {
+    pub format: u16,
+    pub count: u16,
+    pub string_offset: u16,
+    pub name_records: Seq<NameRecord>,
+    pub lang_tag_count: u16,
+    pub lang_tag_records: Seq<LangTagRecord>,
+}
+
+impl TryFromStream for Name {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let table_start = stream.stream_position()?;
+        let format = decode_from_reader(stream)?;
+        let count = decode_from_reader(stream)?;
+        let string_offset: u16 = decode_from_reader(stream)?;
+        let mut name_records = Vec::new();
+        let mut lang_tag_count = 0;
+        let mut lang_tag_records = Vec::new();
+
+        for _ in 0..count {
+            name_records.push(NameRecord::try_from_stream(stream)?);
+        }
+
+        if format == 1 {
+            lang_tag_count = decode_from_reader(stream)?;
+
+            for _ in 0..lang_tag_count {
+                lang_tag_records.push(LangTagRecord::try_from_stream(stream)?);
+            }
+        }
+
+        let storage_start = table_start + string_offset as u64;
+
+        for record in &mut name_records {
+            record.value = read_string(storage_start, record.offset, record.length, stream)?;
+        }
+
+        for record in &mut lang_tag_records {
+            record.value = read_string(storage_start, record.offset, record.length, stream)?;
+        }
+
+        Ok(Self {
+            format,
+            count,
+            string_offset,
+            name_records: name_records.into(),
+            lang_tag_count,
+            lang_tag_records: lang_tag_records.into(),
+        })
+    }
+}
+
+impl Encode for Name {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let count = self.name_records.as_slice().len();
+        let lang_tag_count = self.lang_tag_records.as_slice().len();
+        let mut string_offset = 6 + count * NAME_RECORD_SIZE;
+        let lang_tag_records = match self.format {
+            1 => self.lang_tag_records.as_slice(),
+            _ => &[],
+        };
+
+        if self.format == 1 {
+            string_offset += 2 + lang_tag_count * LANG_TAG_RECORD_SIZE;
+        }
+
+        // the string storage has to start within reach of the 16-bit stringOffset
+        let Ok(string_offset) = u16::try_from(string_offset) else {
+            return Err(EncodeError::Other("name records do not fit in 64 KiB"));
+        };
+
+        let layout = self
+            .name_records
+            .iter()
+            .map(|r| (r.offset, r.length, r.value.as_slice()))
+            .chain(
+                lang_tag_records
+                    .iter()
+                    .map(|r| (r.offset, r.length, r.value.as_slice())),
+            );
+        let mut storage = StringStorage::from_layout(layout).unwrap_or_default();
+
+        self.format.encode(encoder)?;
+        (count as u16).encode(encoder)?;
+        string_offset.encode(encoder)?;
+
+        for record in self.name_records.iter() {
+            let offset = storage.insert(record.value.as_slice(), record.offset)?;
+            record.platform_id.encode(encoder)?;
+            record.encoding_id.encode(encoder)?;
+            record.language_id.encode(encoder)?;
+            record.name_id.encode(encoder)?;
+            (record.value.as_slice().len() as u16).encode(encoder)?;
+            offset.encode(encoder)?;
+        }
+
+        if self.format == 1 {
+            (lang_tag_count as u16).encode(encoder)?;
+
+            for record in lang_tag_records {
+                let offset = storage.insert(record.value.as_slice(), record.offset)?;
+                (record.value.as_slice().len() as u16).encode(encoder)?;
+                offset.encode(encoder)?;
+            }
+        }
+
+        Seq::from(storage.data).encode(encoder)
+    }
+}
+
+impl Name {
+    pub fn get(&self, name_id: NameId, platform_id: u16, language_id: u16) -> Option<String> {
+        self.name_records
+            .iter()
+            .find(|r| {
+                r.name_id == name_id as u16
+                    && r.platform_id == platform_id
+                    && r.language_id == language_id
+            })
+            .and_then(NameRecord::to_string)
+    }
+
+    pub fn get_any(&self, name_id: NameId) -> Option<String> {
+        self.name_records
+            .iter()
+            .filter(|r| r.name_id == name_id as u16)
+            .find_map(NameRecord::to_string)
+    }
+
+    pub fn lang_tag(&self, language_id: u16) -> Option<String> {
+        let index = language_id.checked_sub(0x8000)? as usize;
+        let record = self.lang_tag_records.as_slice().get(index)?;
+        decode_utf16_be(record.value.as_slice())
+    }
+}
+
+#[derive(Debug)]
+pub struct NameRecord {
+    pub platform_id: u16,
+    pub encoding_id: u16,
+    pub language_id: u16,
+    pub name_id: u16,
+    pub length: u16,
+    pub offset: u16,
+    pub value: Seq<u8>,
+}
+
+impl TryFromStream for NameRecord {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read,
+    {
+        Ok(Self {
+            platform_id: decode_from_reader(stream)?,
+            encoding_id: decode_from_reader(stream)?,
+            language_id: decode_from_reader(stream)?,
+            name_id: decode_from_reader(stream)?,
+            length: decode_from_reader(stream)?,
+            offset: decode_from_reader(stream)?,
+            value: Seq::default(),
+        })
+    }
+}
+
+impl NameRecord {
+    pub fn to_string(&self) -> Option<String> {
+        let bytes = self.value.as_slice();
+
+        match (self.platform_id, self.encoding_id) {
+            (PLATFORM_UNICODE, _) => decode_utf16_be(bytes),
+            (PLATFORM_MACINTOSH, 0) => Some(decode_mac_roman(bytes)),
+            (PLATFORM_WINDOWS, 0 | 1 | 10) => decode_utf16_be(bytes),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LangTagRecord {
+    pub length: u16,
+    pub offset: u16,
+    pub value: Seq<u8>,
+}
+
+impl TryFromStream for LangTagRecord {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read,
+    {
+        Ok(Self {
+            length: decode_from_reader(stream)?,
+            offset: decode_from_reader(stream)?,
+            value: Seq::default(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct StringStorage {
+    data: Vec<u8>,
+    fixed: bool,
+}
+
+impl StringStorage {
+    // unmodified tables keep the string layout they were read with
+    fn from_layout<'a, I>(records: I) -> Option<Self>
+    where
+        I: Iterator<Item = (u16, u16, &'a [u8])> + Clone,
+    {
+        let mut data = Vec::new();
+
+        for (offset, length, value) in records.clone() {
+            if length as usize != value.len() {
+                return None;
+            }
+
+            let range = offset as usize..offset as usize + value.len();
+            data.resize(data.len().max(range.end), 0);
+            data[range].copy_from_slice(value);
+        }
+
+        // shared or overlapping strings must still read back as their own values
+        for (offset, _, value) in records {
+            if !data[offset as usize..].starts_with(value) {
+                return None;
+            }
+        }
+
+        Some(Self { data, fixed: true })
+    }
+
+    fn insert(&mut self, value: &[u8], offset: u16) -> Result<u16, EncodeError> {
+        if self.fixed {
+            return Ok(offset);
+        }
+
+        let existing = match value.len() {
+            0 => Some(0),
+            len => self.data.windows(len).position(|w| w == value),
+        };
+        let offset = existing.unwrap_or_else(|| {
+            let offset = self.data.len();
+            self.data.extend_from_slice(value);
+            offset
+        });
+
+        // record offsets and lengths are 16-bit
+        match u16::try_from(offset)
+            .ok()
+            .zip(u16::try_from(value.len()).ok())
+        {
+            Some((offset, _)) => Ok(offset),
+            None => Err(EncodeError::Other(
+                "name strings do not fit in 64 KiB of storage",
+            )),
+        }
+    }
+}
+
+fn read_string<T>(
+    storage_start: u64,
+    offset: u16,
+    length: u16,
+    stream: &mut T,
+) -> Result<Seq<u8>, Error>
+where
+    T: Read + Seek,
+{
+    let position = SeekFrom::Start(storage_start + offset as u64);
+    stream.seek(position)?;
+    stream.read_seq(length as usize)
+}
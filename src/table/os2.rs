@@ -0,0 +1,236 @@
+use crate::{
+    error::Error,
+    sfnt::types::FWord,
+    utils::{bitflag::BitFlag, reader::TryFromStream, types::Opt},
+};
+use bincode::{Decode, Encode};
+use std::io::{Read, Seek};
+
+const FS_TYPE_RESTRICTED: u16 = 1;
+const FS_TYPE_PREVIEW_AND_PRINT: u16 = 2;
+const FS_TYPE_EDITABLE: u16 = 3;
+const FS_TYPE_NO_SUBSETTING: u16 = 8;
+const FS_TYPE_BITMAP_ONLY: u16 = 9;
+
+const FS_SELECTION_ITALIC: u16 = 0;
+const FS_SELECTION_UNDERSCORE: u16 = 1;
+const FS_SELECTION_NEGATIVE: u16 = 2;
+const FS_SELECTION_OUTLINED: u16 = 3;
+const FS_SELECTION_STRIKEOUT: u16 = 4;
+const FS_SELECTION_BOLD: u16 = 5;
+const FS_SELECTION_REGULAR: u16 = 6;
+const FS_SELECTION_USE_TYPO_METRICS: u16 = 7;
+const FS_SELECTION_WWS: u16 = 8;
+const FS_SELECTION_OBLIQUE: u16 = 9;
+
+#[derive(Debug, Encode)]
+pub struct Os2 {
+    pub header: Os2Header,
+    pub code_page_range: Opt<Os2CodePageRange>,
+    pub extended_metrics: Opt<Os2ExtendedMetrics>,
+    pub optical_size: Opt<Os2OpticalSize>,
+}
+
+impl TryFromStream for Os2 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let header = Os2Header::try_from_stream(stream)?;
+        let version = header.version;
+        let mut code_page_range = None;
+        let mut extended_metrics = None;
+        let mut optical_size = None;
+
+        if version >= 1 {
+            code_page_range = Some(Os2CodePageRange::try_from_stream(stream)?);
+        }
+
+        if version >= 2 {
+            extended_metrics = Some(Os2ExtendedMetrics::try_from_stream(stream)?);
+        }
+
+        if version >= 5 {
+            optical_size = Some(Os2OpticalSize::try_from_stream(stream)?);
+        }
+
+        Ok(Self {
+            header,
+            code_page_range: code_page_range.into(),
+            extended_metrics: extended_metrics.into(),
+            optical_size: optical_size.into(),
+        })
+    }
+}
+
+impl Os2 {
+    pub fn fs_type(&self) -> FsType {
+        FsType(self.header.fs_type)
+    }
+
+    pub fn fs_selection(&self) -> FsSelection {
+        FsSelection(self.header.fs_selection)
+    }
+
+    pub fn unicode_ranges(&self) -> [u32; 4] {
+        [
+            self.header.ul_unicode_range1,
+            self.header.ul_unicode_range2,
+            self.header.ul_unicode_range3,
+            self.header.ul_unicode_range4,
+        ]
+    }
+
+    pub fn has_unicode_range(&self, bit: u32) -> bool {
+        let ranges = self.unicode_ranges();
+        let word = ranges.get(bit as usize / 32).copied().unwrap_or_default();
+        word.has(bit % 32)
+    }
+
+    pub fn code_page_ranges(&self) -> Option<[u32; 2]> {
+        let range = self.code_page_range.as_option()?;
+        Some([range.ul_code_page_range1, range.ul_code_page_range2])
+    }
+
+    pub fn x_height(&self) -> Option<FWord> {
+        self.extended_metrics.as_option().map(|m| m.sx_height)
+    }
+
+    pub fn cap_height(&self) -> Option<FWord> {
+        self.extended_metrics.as_option().map(|m| m.s_cap_height)
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct Os2Header {
+    pub version: u16,
+    pub x_avg_char_width: FWord,
+    pub us_weight_class: u16,
+    pub us_width_class: u16,
+    pub fs_type: u16,
+    pub y_subscript_x_size: FWord,
+    pub y_subscript_y_size: FWord,
+    pub y_subscript_x_offset: FWord,
+    pub y_subscript_y_offset: FWord,
+    pub y_superscript_x_size: FWord,
+    pub y_superscript_y_size: FWord,
+    pub y_superscript_x_offset: FWord,
+    pub y_superscript_y_offset: FWord,
+    pub y_strikeout_size: FWord,
+    pub y_strikeout_position: FWord,
+    pub s_family_class: i16,
+    pub panose: [u8; 10],
+    pub ul_unicode_range1: u32,
+    pub ul_unicode_range2: u32,
+    pub ul_unicode_range3: u32,
+    pub ul_unicode_range4: u32,
+    pub ach_vend_id: u32,
+    pub fs_selection: u16,
+    pub us_first_char_index: u16,
+    pub us_last_char_index: u16,
+    pub s_typo_ascender: FWord,
+    pub s_typo_descender: FWord,
+    pub s_typo_line_gap: FWord,
+    pub us_win_ascent: u16,
+    pub us_win_descent: u16,
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct Os2CodePageRange {
+    pub ul_code_page_range1: u32,
+    pub ul_code_page_range2: u32,
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct Os2ExtendedMetrics {
+    pub sx_height: FWord,
+    pub s_cap_height: FWord,
+    pub us_default_char: u16,
+    pub us_break_char: u16,
+    pub us_max_context: u16,
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct Os2OpticalSize {
+    pub us_lower_optical_point_size: u16,
+    pub us_upper_optical_point_size: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingPermission {
+    Installable,
+    Restricted,
+    PreviewAndPrint,
+    Editable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsType(pub u16);
+
+impl FsType {
+    pub fn embedding_permission(self) -> EmbeddingPermission {
+        // fonts that set several bits grant the least restrictive of them
+        if self.0.has(FS_TYPE_EDITABLE) {
+            EmbeddingPermission::Editable
+        } else if self.0.has(FS_TYPE_PREVIEW_AND_PRINT) {
+            EmbeddingPermission::PreviewAndPrint
+        } else if self.0.has(FS_TYPE_RESTRICTED) {
+            EmbeddingPermission::Restricted
+        } else {
+            EmbeddingPermission::Installable
+        }
+    }
+
+    pub fn no_subsetting(self) -> bool {
+        self.0.has(FS_TYPE_NO_SUBSETTING)
+    }
+
+    pub fn bitmap_only(self) -> bool {
+        self.0.has(FS_TYPE_BITMAP_ONLY)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsSelection(pub u16);
+
+impl FsSelection {
+    pub fn italic(self) -> bool {
+        self.0.has(FS_SELECTION_ITALIC)
+    }
+
+    pub fn underscore(self) -> bool {
+        self.0.has(FS_SELECTION_UNDERSCORE)
+    }
+
+    pub fn negative(self) -> bool {
+        self.0.has(FS_SELECTION_NEGATIVE)
+    }
+
+    pub fn outlined(self) -> bool {
+        self.0.has(FS_SELECTION_OUTLINED)
+    }
+
+    pub fn strikeout(self) -> bool {
+        self.0.has(FS_SELECTION_STRIKEOUT)
+    }
+
+    pub fn bold(self) -> bool {
+        self.0.has(FS_SELECTION_BOLD)
+    }
+
+    pub fn regular(self) -> bool {
+        self.0.has(FS_SELECTION_REGULAR)
+    }
+
+    pub fn use_typo_metrics(self) -> bool {
+        self.0.has(FS_SELECTION_USE_TYPO_METRICS)
+    }
+
+    pub fn wws(self) -> bool {
+        self.0.has(FS_SELECTION_WWS)
+    }
+
+    pub fn oblique(self) -> bool {
+        self.0.has(FS_SELECTION_OBLIQUE)
+    }
+}
@@ -0,0 +1,251 @@
+mod mac_names;
+
+pub use mac_names::MAC_GLYPH_NAMES;
+
+use crate::{
+    error::Error,
+    sfnt::types::{FWord, Fixed},
+    utils::{
+        bincode::decode_from_reader,
+        reader::{ReadSeq, TryFromStream},
+        types::{EncodeSeq, Seq},
+    },
+};
+use bincode::{enc::Encoder, error::EncodeError, Decode, Encode};
+use std::io::{Read, Seek};
+
+const VERSION_1_0: Fixed = 0x00010000;
+const VERSION_2_0: Fixed = 0x00020000;
+const VERSION_2_5: Fixed = 0x00025000;
+
+#[derive(Debug, Encode)]
+pub struct Post {
+    pub header: PostHeader,
+    pub glyph_names: PostGlyphNames,
+}
+
+impl Post {
+    pub fn try_from_params<T>(length: u32, stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let table_start = stream.stream_position()?;
+        let table_end = table_start + length as u64;
+        let header = PostHeader::try_from_stream(stream)?;
+
+        let glyph_names = match header.version {
+            VERSION_2_0 => PostGlyphNames::Version2(PostV2::try_from_params(table_end, stream)?),
+            VERSION_2_5 => PostGlyphNames::Version25(PostV25::try_from_stream(stream)?),
+            _ => PostGlyphNames::None,
+        };
+
+        Ok(Self {
+            header,
+            glyph_names,
+        })
+    }
+
+    pub fn set_glyph_names(&mut self, glyph_names: PostV2) {
+        self.header.version = VERSION_2_0;
+        self.glyph_names = PostGlyphNames::Version2(glyph_names);
+    }
+
+    pub fn italic_angle(&self) -> f32 {
+        self.header.italic_angle as i32 as f32 / 65536.0
+    }
+
+    pub fn is_fixed_pitch(&self) -> bool {
+        self.header.is_fixed_pitch != 0
+    }
+
+    pub fn num_glyph_names(&self) -> usize {
+        match (&self.glyph_names, self.header.version) {
+            (PostGlyphNames::Version2(v2), _) => v2.glyph_name_index.as_slice().len(),
+            (PostGlyphNames::Version25(v25), _) => v25.offset.as_slice().len(),
+            (PostGlyphNames::None, VERSION_1_0) => MAC_GLYPH_NAMES.len(),
+            (PostGlyphNames::None, _) => 0,
+        }
+    }
+
+    pub fn glyph_name(&self, glyph_id: u16) -> Option<&str> {
+        let glyph_id = glyph_id as usize;
+
+        match (&self.glyph_names, self.header.version) {
+            (PostGlyphNames::Version2(v2), _) => v2.glyph_name(glyph_id),
+            (PostGlyphNames::Version25(v25), _) => v25.glyph_name(glyph_id),
+            (PostGlyphNames::None, VERSION_1_0) => MAC_GLYPH_NAMES.get(glyph_id).copied(),
+            (PostGlyphNames::None, _) => None,
+        }
+    }
+
+    pub fn glyph_index(&self, name: &str) -> Option<u16> {
+        (0..self.num_glyph_names())
+            .map(|i| i as u16)
+            .find(|&i| self.glyph_name(i) == Some(name))
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct PostHeader {
+    pub version: Fixed,
+    pub italic_angle: Fixed,
+    pub underline_position: FWord,
+    pub underline_thickness: FWord,
+    pub is_fixed_pitch: u32,
+    pub min_mem_type42: u32,
+    pub max_mem_type42: u32,
+    pub min_mem_type1: u32,
+    pub max_mem_type1: u32,
+}
+
+#[derive(Debug)]
+pub enum PostGlyphNames {
+    None,
+    Version2(PostV2),
+    Version25(PostV25),
+}
+
+impl Encode for PostGlyphNames {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self {
+            PostGlyphNames::None => Ok(()),
+            PostGlyphNames::Version2(value) => value.encode(encoder),
+            PostGlyphNames::Version25(value) => value.encode(encoder),
+        }
+    }
+}
+
+#[derive(Debug, Encode)]
+pub struct PostV2 {
+    pub num_glyphs: u16,
+    pub glyph_name_index: Seq<u16>,
+    pub names: Seq<PascalString>,
+}
+
+impl PostV2 {
+    pub fn from_names<'a, I>(names: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut custom_names = Vec::<&str>::new();
+        let mut glyph_name_index = Vec::new();
+
+        for name in names {
+            let index = match MAC_GLYPH_NAMES.iter().position(|&n| n == name) {
+                Some(index) => index,
+                None => match custom_names.iter().position(|&n| n == name) {
+                    Some(index) => MAC_GLYPH_NAMES.len() + index,
+                    None => {
+                        custom_names.push(name);
+                        MAC_GLYPH_NAMES.len() + custom_names.len() - 1
+                    }
+                },
+            };
+
+            glyph_name_index.push(index as u16);
+        }
+
+        let names = custom_names
+            .into_iter()
+            .map(|name| {
+                // Pascal strings hold at most 255 bytes, cut before a character that would not fit
+                let end = (0..=name.len().min(u8::MAX as usize))
+                    .rev()
+                    .find(|&i| name.is_char_boundary(i))
+                    .unwrap_or_default();
+                let data = &name.as_bytes()[..end];
+
+                PascalString {
+                    length: data.len() as u8,
+                    data: data.to_vec().into(),
+                }
+            })
+            .collect();
+
+        Self {
+            num_glyphs: glyph_name_index.len() as u16,
+            glyph_name_index: glyph_name_index.into(),
+            names,
+        }
+    }
+
+    fn try_from_params<T>(table_end: u64, stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let num_glyphs = decode_from_reader(stream)?;
+        let glyph_name_index = stream.read_seq(num_glyphs as usize)?;
+        let mut names = Vec::new();
+
+        while stream.stream_position()? < table_end {
+            names.push(PascalString::try_from_stream(stream)?);
+        }
+
+        Ok(Self {
+            num_glyphs,
+            glyph_name_index,
+            names: names.into(),
+        })
+    }
+
+    fn glyph_name(&self, glyph_id: usize) -> Option<&str> {
+        let index = *self.glyph_name_index.as_slice().get(glyph_id)? as usize;
+
+        match index.checked_sub(MAC_GLYPH_NAMES.len()) {
+            Some(custom) => self.names.as_slice().get(custom)?.as_str(),
+            None => Some(MAC_GLYPH_NAMES[index]),
+        }
+    }
+}
+
+#[derive(Debug, Encode)]
+pub struct PostV25 {
+    pub num_glyphs: u16,
+    pub offset: Seq<i8>,
+}
+
+impl TryFromStream for PostV25 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let num_glyphs = decode_from_reader(stream)?;
+        let offset = stream.read_seq(num_glyphs as usize)?;
+
+        Ok(Self { num_glyphs, offset })
+    }
+}
+
+impl PostV25 {
+    fn glyph_name(&self, glyph_id: usize) -> Option<&str> {
+        let offset = *self.offset.as_slice().get(glyph_id)? as isize;
+        let index = (glyph_id as isize).checked_add(offset)?;
+        MAC_GLYPH_NAMES.get(usize::try_from(index).ok()?).copied()
+    }
+}
+
+#[derive(Debug, Encode)]
+pub struct PascalString {
+    pub length: u8,
+    pub data: Seq<u8>,
+}
+
+impl EncodeSeq for PascalString {}
+
+impl TryFromStream for PascalString {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let length = decode_from_reader(stream)?;
+        let data = stream.read_seq(length as usize)?;
+
+        Ok(Self { length, data })
+    }
+}
+
+impl PascalString {
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(self.data.as_slice()).ok()
+    }
+}
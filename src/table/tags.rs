@@ -2,15 +2,44 @@ use std::cmp::Ordering;
 
 pub type Tag = u32;
 
+pub const BASE: u32 = 1111577413;
+pub const CBDT: u32 = 1128416340;
+pub const CBLC: u32 = 1128418371;
 pub const CMAP: u32 = 1668112752;
+pub const COLR: u32 = 1129270354;
+pub const CVT: u32 = 1668707360;
+pub const DSIG: u32 = 1146308935;
+pub const EBDT: u32 = 1161970772;
+pub const EBLC: u32 = 1161972803;
+pub const EBSC: u32 = 1161974595;
+pub const FPGM: u32 = 1718642541;
+pub const GASP: u32 = 1734439792;
+pub const GDEF: u32 = 1195656518;
 pub const GLYF: u32 = 1735162214;
+pub const GPOS: u32 = 1196445523;
+pub const GSUB: u32 = 1196643650;
+pub const GVAR: u32 = 1735811442;
+pub const HDMX: u32 = 1751412088;
 pub const HEAD: u32 = 1751474532;
 pub const HHEA: u32 = 1751672161;
 pub const HMTX: u32 = 1752003704;
+pub const HVAR: u32 = 1213612370;
+pub const KERN: u32 = 1801810542;
 pub const LOCA: u32 = 1819239265;
+pub const LTSH: u32 = 1280594760;
+pub const MATH: u32 = 1296127048;
 pub const MAXP: u32 = 1835104368;
 pub const NAME: u32 = 1851878757;
+pub const OS_2: u32 = 1330851634;
+pub const PCLT: u32 = 1346587732;
 pub const POST: u32 = 1886352244;
+pub const PREP: u32 = 1886545264;
+pub const SBIX: u32 = 1935829368;
+pub const VDMX: u32 = 1447316824;
+pub const VHEA: u32 = 1986553185;
+pub const VMTX: u32 = 1986884728;
+pub const VORG: u32 = 1448038983;
+pub const VVAR: u32 = 1448493394;
 
 pub const REQUIRED_TAGS: [Tag; 9] = [CMAP, GLYF, HEAD, HHEA, HMTX, LOCA, MAXP, NAME, POST];
 
@@ -25,6 +54,8 @@ fn tag_priority(tag: Tag) -> u8 {
         CMAP => 7,
         NAME => 8,
         POST => 9,
+        VHEA => 10,
+        VMTX => 11,
         _ => 255,
     }
 }
@@ -34,3 +65,45 @@ pub fn compare_tags(a: Tag, b: Tag) -> Ordering {
     let b_priority = tag_priority(b);
     a_priority.cmp(&b_priority)
 }
+
+fn layout_priority(tag: Tag) -> u8 {
+    match tag {
+        HEAD => 1,
+        HHEA => 2,
+        MAXP => 3,
+        OS_2 => 4,
+        HMTX => 5,
+        LTSH => 6,
+        VDMX => 7,
+        HDMX => 8,
+        CMAP => 9,
+        FPGM => 10,
+        PREP => 11,
+        CVT => 12,
+        LOCA => 13,
+        GLYF => 14,
+        KERN => 15,
+        NAME => 16,
+        POST => 17,
+        GASP => 18,
+        PCLT => 19,
+        DSIG => 20,
+        _ => 255,
+    }
+}
+
+pub fn compare_layout_tags(a: Tag, b: Tag) -> Ordering {
+    let a_priority = layout_priority(a);
+    let b_priority = layout_priority(b);
+    a_priority.cmp(&b_priority).then(a.cmp(&b))
+}
+
+pub fn tag_to_string(tag: Tag) -> String {
+    tag.to_be_bytes()
+        .iter()
+        .map(|&b| match b.is_ascii_graphic() || b == b' ' {
+            true => b as char,
+            false => '?',
+        })
+        .collect()
+}
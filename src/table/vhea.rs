@@ -0,0 +1,23 @@
+use crate::{
+    sfnt::types::{FWord, Fixed, UFWord},
+    utils::types::Padding,
+};
+use bincode::{Decode, Encode};
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Vhea {
+    pub version: Fixed,
+    pub ascent: FWord,
+    pub descent: FWord,
+    pub line_gap: FWord,
+    pub advance_height_max: UFWord,
+    pub min_top_side_bearing: FWord,
+    pub min_bottom_side_bearing: FWord,
+    pub y_max_extent: FWord,
+    pub carret_slope_rise: i16,
+    pub carret_slope_run: i16,
+    pub carret_offset: FWord,
+    pub _reserved: Padding<8>,
+    pub metric_data_format: i16,
+    pub num_of_long_ver_metrics: u16,
+}
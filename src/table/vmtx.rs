@@ -0,0 +1,119 @@
+use crate::{
+    error::Error,
+    sfnt::types::FWord,
+    table::{
+        long_metric::{find_metric, read_metric, read_metrics, split_metrics, LongMetric},
+        tags::{self, Tag},
+        FontTable, GetFontTable,
+    },
+    utils::types::Seq,
+};
+use bincode::{Decode, Encode};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek},
+};
+
+#[derive(Debug, Encode)]
+pub struct Vmtx {
+    pub v_metrics: Seq<LongVerMetric>,
+    pub top_side_bearing: Seq<FWord>,
+}
+
+impl Vmtx {
+    pub fn try_from_params<T>(
+        length: u32,
+        tables: &BTreeMap<Tag, FontTable>,
+        stream: &mut T,
+    ) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let num_glyphs = tables.maxp()?.num_glyphs as usize;
+        let num_of_long_ver_metrics = tables.vhea()?.num_of_long_ver_metrics as usize;
+        let (v_metrics, top_side_bearing) = read_metrics(
+            tags::VMTX,
+            "vhea.numOfLongVerMetrics",
+            length,
+            num_of_long_ver_metrics,
+            num_glyphs,
+            stream,
+        )?;
+
+        Ok(Self {
+            v_metrics,
+            top_side_bearing,
+        })
+    }
+
+    pub fn from_metrics(metrics: &[LongVerMetric]) -> Self {
+        let (v_metrics, top_side_bearing) = split_metrics(metrics);
+
+        Self {
+            v_metrics,
+            top_side_bearing,
+        }
+    }
+
+    pub fn metric(&self, glyph_id: u16) -> Option<LongVerMetric> {
+        let top_side_bearing = self.top_side_bearing.as_slice();
+        find_metric(self.v_metrics.as_slice(), top_side_bearing, glyph_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VmtxRef<'a> {
+    data: &'a [u8],
+    num_of_long_ver_metrics: usize,
+    num_glyphs: usize,
+}
+
+impl<'a> VmtxRef<'a> {
+    pub fn new(data: &'a [u8], num_of_long_ver_metrics: usize, num_glyphs: usize) -> Self {
+        Self {
+            data,
+            num_of_long_ver_metrics,
+            num_glyphs,
+        }
+    }
+
+    pub fn metric(&self, glyph_id: u16) -> Option<LongVerMetric> {
+        read_metric(
+            self.data,
+            self.num_of_long_ver_metrics,
+            self.num_glyphs,
+            glyph_id,
+        )
+    }
+
+    pub fn advance_height(&self, glyph_id: u16) -> Option<u16> {
+        self.metric(glyph_id).map(|m| m.advance_height)
+    }
+
+    pub fn top_side_bearing(&self, glyph_id: u16) -> Option<i16> {
+        self.metric(glyph_id).map(|m| m.top_side_bearing)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+pub struct LongVerMetric {
+    pub advance_height: u16,
+    pub top_side_bearing: i16,
+}
+
+impl LongMetric for LongVerMetric {
+    fn new(advance_height: u16, top_side_bearing: i16) -> Self {
+        Self {
+            advance_height,
+            top_side_bearing,
+        }
+    }
+
+    fn advance(&self) -> u16 {
+        self.advance_height
+    }
+
+    fn side_bearing(&self) -> i16 {
+        self.top_side_bearing
+    }
+}
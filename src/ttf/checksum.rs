@@ -0,0 +1,72 @@
+use crate::{
+    error::Error,
+    table::tags::{self, Tag},
+    ttf::font_dir::TableDirEntry,
+    utils::bytes::read_be,
+};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek, SeekFrom},
+};
+
+pub const CHECKSUM_MAGIC: u32 = 0xB1B0AFBA;
+
+pub const CHECKSUM_ADJUSTMENT_OFFSET: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumMismatch {
+    Table {
+        tag: Tag,
+        expected: u32,
+        actual: u32,
+    },
+    Adjustment {
+        expected: u32,
+        actual: u32,
+    },
+}
+
+pub fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_be_bytes(word)
+        })
+        .fold(0, u32::wrapping_add)
+}
+
+// checksums of the table bytes as they were read, so verification reflects the file
+// rather than a re-encode of the parsed tables
+#[derive(Debug, Default)]
+pub struct LoadedChecksums {
+    pub tables: BTreeMap<Tag, u32>,
+    pub adjustment: Option<u32>,
+}
+
+impl LoadedChecksums {
+    pub fn read_table<T>(&mut self, entry: &TableDirEntry, stream: &mut T) -> Result<(), Error>
+    where
+        T: Read + Seek,
+    {
+        let mut bytes = Vec::new();
+        stream.seek(SeekFrom::Start(entry.offset.into()))?;
+        stream
+            .by_ref()
+            .take(entry.length.into())
+            .read_to_end(&mut bytes)?;
+
+        let position = CHECKSUM_ADJUSTMENT_OFFSET;
+        let adjustment = bytes
+            .get_mut(position..position + 4)
+            .filter(|_| entry.tag == tags::HEAD);
+
+        if let Some(adjustment) = adjustment {
+            self.adjustment = read_be(adjustment, 0);
+            adjustment.fill(0);
+        }
+
+        self.tables.insert(entry.tag, checksum(&bytes));
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{
+    error::Error,
+    table::tags::{self, compare_layout_tags, Tag},
+    ttf::{
+        checksum::{checksum, CHECKSUM_ADJUSTMENT_OFFSET, CHECKSUM_MAGIC},
+        collection_dir::{TtcDsigHeader, TtcHeader, TTC_TAG},
+        font::Font,
+        font_dir::{
+            padded_len, FontDirectory, OffsetSubtable, TableDirEntry, OFFSET_SUBTABLE_SIZE,
+            TABLE_DIR_ENTRY_SIZE,
+        },
+    },
+    utils::{
+        bincode::{decode_from_reader, encode_to_vec},
+        reader::TryFromStream,
+    },
+};
+use bincode::{
+    enc::{write::Writer, Encoder},
+    error::EncodeError,
+    Encode,
+};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    io::{Read, Seek, SeekFrom},
+};
+
+const TTC_HEADER_SIZE: usize = 12;
+const TTC_DSIG_HEADER_SIZE: usize = 12;
+
+#[derive(Debug)]
+pub struct FontCollection {
+    pub fonts: Vec<Font>,
+    pub dsig: Option<Vec<u8>>,
+}
+
+impl TryFromStream for FontCollection {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let start = stream.stream_position()?;
+        let tag: Tag = decode_from_reader(stream)?;
+        stream.seek(SeekFrom::Start(start))?;
+
+        if tag != TTC_TAG {
+            return Ok(Self::new(vec![Font::try_from_stream(stream)?]));
+        }
+
+        let header = TtcHeader::try_from_stream(stream)?;
+        let mut fonts = Vec::new();
+
+        for &offset in header.table_directory_offsets.iter() {
+            stream.seek(SeekFrom::Start(start + offset as u64))?;
+            fonts.push(Font::from_sfnt_stream(stream)?);
+        }
+
+        let dsig = match header.dsig.as_option() {
+            Some(dsig) if dsig.dsig_tag == tags::DSIG && dsig.dsig_length > 0 => {
+                stream.seek(SeekFrom::Start(start + dsig.dsig_offset as u64))?;
+                let mut bytes = Vec::new();
+                let length = dsig.dsig_length as usize;
+                stream.take(length as u64).read_to_end(&mut bytes)?;
+
+                if bytes.len() != length {
+                    return Err(Error::InvalidCollection("DSIG table is truncated"));
+                }
+
+                Some(bytes)
+            }
+            _ => None,
+        };
+
+        Ok(Self { fonts, dsig })
+    }
+}
+
+impl Encode for FontCollection {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let tables = self
+            .fonts
+            .iter()
+            .map(Font::encode_tables)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let header_size = match self.dsig {
+            Some(_) => TTC_HEADER_SIZE + TTC_DSIG_HEADER_SIZE,
+            None => TTC_HEADER_SIZE,
+        } + self.fonts.len() * 4;
+        let directories_size = tables
+            .iter()
+            .map(|t| OFFSET_SUBTABLE_SIZE + t.len() * TABLE_DIR_ENTRY_SIZE)
+            .sum::<usize>();
+
+        let mut table_offsets = Vec::new();
+        let mut shared = HashMap::<u64, Vec<(usize, &[u8])>>::new();
+        let mut data = Vec::new();
+
+        for font_tables in &tables {
+            let mut order = font_tables.keys().copied().collect::<Vec<_>>();
+            order.sort_by(|&a, &b| compare_layout_tags(a, b));
+
+            let mut offsets = BTreeMap::new();
+
+            for tag in order {
+                let table = font_tables[&tag].as_slice();
+                let candidates = shared.entry(content_hash(table)).or_default();
+
+                let offset = match candidates.iter().find(|(_, t)| *t == table) {
+                    Some(&(offset, _)) => offset,
+                    None => {
+                        let offset = header_size + directories_size + data.len();
+                        candidates.push((offset, table));
+                        data.extend(table);
+                        data.resize(padded_len(data.len()), 0);
+                        offset
+                    }
+                };
+
+                offsets.insert(tag, offset);
+            }
+
+            table_offsets.push(offsets);
+        }
+
+        let mut directories = Vec::new();
+        let mut table_directory_offsets = Vec::new();
+        let mut adjusted = HashSet::new();
+
+        for (font, (font_tables, offsets)) in
+            self.fonts.iter().zip(tables.iter().zip(&table_offsets))
+        {
+            let scaler_type = font.scaler_type();
+            let offset_subtable = OffsetSubtable::new(scaler_type, font_tables.len() as u16);
+            let table_directory = font_tables
+                .iter()
+                .map(|(&tag, table)| TableDirEntry {
+                    tag,
+                    check_sum: checksum(table),
+                    offset: offsets[&tag] as u32,
+                    length: table.len() as u32,
+                })
+                .collect();
+            let directory = FontDirectory {
+                offset_subtable,
+                table_directory,
+            };
+
+            let bytes = encode_to_vec(&directory)?;
+            table_directory_offsets.push((header_size + directories.len()) as u32);
+            set_checksum_adjustment(
+                &directory,
+                &bytes,
+                &mut adjusted,
+                header_size + directories_size,
+                &mut data,
+            );
+            directories.extend(bytes);
+        }
+
+        let dsig = match &self.dsig {
+            Some(dsig) => {
+                let offset = header_size + directories.len() + data.len();
+                data.extend(dsig);
+                data.resize(padded_len(data.len()), 0);
+
+                Some(TtcDsigHeader {
+                    dsig_tag: tags::DSIG,
+                    dsig_length: dsig.len() as u32,
+                    dsig_offset: offset as u32,
+                })
+            }
+            None => None,
+        };
+
+        let header = TtcHeader {
+            ttc_tag: TTC_TAG,
+            major_version: if dsig.is_some() { 2 } else { 1 },
+            minor_version: 0,
+            num_fonts: self.fonts.len() as u32,
+            table_directory_offsets: table_directory_offsets.into(),
+            dsig: dsig.into(),
+        };
+
+        header.encode(encoder)?;
+        encoder.writer().write(&directories)?;
+        encoder.writer().write(&data)
+    }
+}
+
+impl FontCollection {
+    pub fn new(fonts: Vec<Font>) -> Self {
+        Self { fonts, dsig: None }
+    }
+}
+
+fn set_checksum_adjustment(
+    directory: &FontDirectory,
+    directory_bytes: &[u8],
+    adjusted: &mut HashSet<u32>,
+    data_start: usize,
+    data: &mut [u8],
+) {
+    let Some(head) = directory
+        .table_directory
+        .iter()
+        .find(|e| e.tag == tags::HEAD)
+    else {
+        return;
+    };
+
+    // a head shared by several members can only balance one of them, so the first one wins
+    if !adjusted.insert(head.offset) {
+        return;
+    }
+
+    let total = directory
+        .table_directory
+        .iter()
+        .map(|e| e.check_sum)
+        .fold(checksum(directory_bytes), u32::wrapping_add);
+    let adjustment = CHECKSUM_MAGIC.wrapping_sub(total);
+    let position = head.offset as usize - data_start + CHECKSUM_ADJUSTMENT_OFFSET;
+
+    if let Some(bytes) = data.get_mut(position..position + 4) {
+        bytes.copy_from_slice(&adjustment.to_be_bytes());
+    }
+}
+
+fn content_hash(table: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    table.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,62 @@
+use crate::{
+    error::Error,
+    table::tags::Tag,
+    utils::{
+        bincode::decode_from_reader,
+        reader::{ReadSeq, TryFromStream},
+        types::{Opt, Seq},
+    },
+};
+use bincode::{Decode, Encode};
+use std::io::{Read, Seek};
+
+pub const TTC_TAG: Tag = 0x74746366;
+
+#[derive(Debug, Encode)]
+pub struct TtcHeader {
+    pub ttc_tag: Tag,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub num_fonts: u32,
+    pub table_directory_offsets: Seq<u32>,
+    pub dsig: Opt<TtcDsigHeader>,
+}
+
+impl TryFromStream for TtcHeader {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let ttc_tag: Tag = decode_from_reader(stream)?;
+
+        if ttc_tag != TTC_TAG {
+            return Err(Error::InvalidCollection("missing 'ttcf' tag"));
+        }
+
+        let major_version: u16 = decode_from_reader(stream)?;
+        let minor_version: u16 = decode_from_reader(stream)?;
+        let num_fonts: u32 = decode_from_reader(stream)?;
+        let table_directory_offsets = stream.read_seq(num_fonts as usize)?;
+        let mut dsig = None;
+
+        if major_version >= 2 {
+            dsig = Some(TtcDsigHeader::try_from_stream(stream)?);
+        }
+
+        Ok(Self {
+            ttc_tag,
+            major_version,
+            minor_version,
+            num_fonts,
+            table_directory_offsets,
+            dsig: dsig.into(),
+        })
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct TtcDsigHeader {
+    pub dsig_tag: Tag,
+    pub dsig_length: u32,
+    pub dsig_offset: u32,
+}
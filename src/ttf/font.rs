@@ -1,26 +1,85 @@
 use crate::{
     error::Error,
     table::{
-        tags::{compare_tags, Tag},
-        FontTable,
+        tags::{self, compare_layout_tags, compare_tags, Tag, REQUIRED_TAGS},
+        Cmap, FontTable, GetFontTable, Glyf, Head, Hhea, Hmtx, Loca, LocaFormat, Maxp, Name, Os2,
+        Post, Vhea, Vmtx,
     },
-    ttf::font_dir::FontDirectory,
-    utils::{reader::TryFromStream, types::Seq},
+    ttf::{
+        checksum::{
+            checksum, ChecksumMismatch, LoadedChecksums, CHECKSUM_ADJUSTMENT_OFFSET, CHECKSUM_MAGIC,
+        },
+        collection_dir::TTC_TAG,
+        font_dir::{
+            padded_len, FontDirectory, OffsetSubtable, TableDirEntry, OFFSET_SUBTABLE_SIZE,
+            TABLE_DIR_ENTRY_SIZE,
+        },
+    },
+    utils::{
+        bincode::{decode_from_reader, encode_to_vec},
+        reader::TryFromStream,
+        types::Seq,
+    },
+    woff::{Woff, WOFF_SIGNATURE},
+    woff2::{Woff2, WOFF2_SIGNATURE},
+};
+use bincode::{
+    enc::{write::Writer, Encoder},
+    error::EncodeError,
+    Encode,
 };
-use bincode::{enc::Encoder, error::EncodeError, Encode};
 use std::{
     collections::BTreeMap,
-    io::{Read, Seek},
+    io::{Read, Seek, SeekFrom},
 };
 
+pub const MAX_SFNT_SIZE: u64 = 1 << 28;
+
 #[derive(Debug)]
 pub struct Font {
-    pub font_directory: FontDirectory,
-    pub font_tables: BTreeMap<Tag, FontTable>,
+    font_directory: FontDirectory,
+    font_tables: BTreeMap<Tag, FontTable>,
+    loaded_checksums: LoadedChecksums,
 }
 
 impl TryFromStream for Font {
     fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let start = stream.stream_position()?;
+        let signature: u32 = decode_from_reader(stream)?;
+        stream.seek(SeekFrom::Start(start))?;
+
+        match signature {
+            WOFF_SIGNATURE => Ok(Woff::try_from_stream(stream)?.font),
+            WOFF2_SIGNATURE => Ok(Woff2::try_from_stream(stream)?.font),
+            TTC_TAG => Err(Error::UnexpectedCollection),
+            _ => Self::from_sfnt_stream(stream),
+        }
+    }
+}
+
+impl Encode for Font {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let (_, bytes) = self.encode_sfnt()?;
+        encoder.writer().write(&bytes)
+    }
+}
+
+impl Font {
+    pub fn new(scaler_type: u32) -> Self {
+        Self {
+            font_directory: FontDirectory {
+                offset_subtable: OffsetSubtable::new(scaler_type, 0),
+                table_directory: Seq::default(),
+            },
+            font_tables: BTreeMap::new(),
+            loaded_checksums: LoadedChecksums::default(),
+        }
+    }
+
+    pub fn from_sfnt_stream<T>(stream: &mut T) -> Result<Self, Error>
     where
         T: Read + Seek,
     {
@@ -31,36 +90,281 @@ impl TryFromStream for Font {
         }
 
         let mut font_tables = BTreeMap::new();
+        let mut loaded_checksums = LoadedChecksums::default();
         let mut table_entries = font_directory.table_directory.iter().collect::<Vec<_>>();
         table_entries.sort_by(|a, b| compare_tags(a.tag, b.tag));
 
         for entry in table_entries {
             let table = FontTable::try_from_params(entry, &font_tables, stream)?;
             font_tables.insert(entry.tag, table);
+            loaded_checksums.read_table(entry, stream)?;
         }
 
         Ok(Self {
             font_directory,
             font_tables,
+            loaded_checksums,
         })
     }
-}
 
-impl Encode for Font {
-    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        let sorted_entries = self.font_directory.get_sorted_table_entries();
+    // the directory as it was read, kept for checksum verification; it does not follow
+    // insert_table or remove_table, encode_sfnt returns the one for the current tables
+    pub fn font_directory(&self) -> &FontDirectory {
+        &self.font_directory
+    }
+
+    pub fn font_tables(&self) -> &BTreeMap<Tag, FontTable> {
+        &self.font_tables
+    }
+
+    pub fn scaler_type(&self) -> u32 {
+        self.font_directory.offset_subtable.scaler_type
+    }
+
+    pub fn into_tables(self) -> BTreeMap<Tag, FontTable> {
+        self.font_tables
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = Tag> + '_ {
+        self.font_tables.keys().copied()
+    }
+
+    pub fn contains_table(&self, tag: Tag) -> bool {
+        self.font_tables.contains_key(&tag)
+    }
 
-        self.font_directory.encode(encoder)?;
+    pub fn table(&self, tag: Tag) -> Option<&FontTable> {
+        self.font_tables.get(&tag)
+    }
+
+    pub fn table_mut(&mut self, tag: Tag) -> Option<&mut FontTable> {
+        self.font_tables.get_mut(&tag)
+    }
+
+    pub fn insert_table(&mut self, tag: Tag, table: FontTable) -> Result<Option<FontTable>, Error> {
+        // raw bytes are only kept for tables the parser would not decode either
+        let decoded = REQUIRED_TAGS.contains(&tag) || matches!(tag, tags::OS_2 | tags::VHEA | tags::VMTX);
+
+        match table.tag() {
+            Some(expected) if expected != tag => Err(Error::MismatchedTable(tag)),
+            None if decoded => Err(Error::MismatchedTable(tag)),
+            _ => Ok(self.font_tables.insert(tag, table)),
+        }
+    }
+
+    pub fn remove_table(&mut self, tag: Tag) -> Result<Option<FontTable>, Error> {
+        if REQUIRED_TAGS.contains(&tag) {
+            return Err(Error::RequiredTable(tag));
+        }
+
+        Ok(self.font_tables.remove(&tag))
+    }
+
+    pub fn encode_tables(&self) -> Result<BTreeMap<Tag, Vec<u8>>, EncodeError> {
+        self.font_tables
+            .iter()
+            .map(|(&tag, table)| Ok((tag, self.encode_table(tag, table)?)))
+            .collect()
+    }
+
+    pub fn encode_sfnt(&self) -> Result<(FontDirectory, Vec<u8>), EncodeError> {
+        let scaler_type = self.font_directory.offset_subtable.scaler_type;
+        write_sfnt(scaler_type, self.encode_tables()?)
+    }
+
+    pub fn verify_checksums(&self) -> Result<Vec<ChecksumMismatch>, Error> {
+        let loaded = &self.loaded_checksums;
+        let mut mismatches = Vec::new();
+        let mut total = checksum(&encode_to_vec(&self.font_directory)?);
+
+        for entry in self.font_directory.table_directory.iter() {
+            let Some(&actual) = loaded.tables.get(&entry.tag) else {
+                continue;
+            };
+
+            total = total.wrapping_add(actual);
+
+            if actual != entry.check_sum {
+                mismatches.push(ChecksumMismatch::Table {
+                    tag: entry.tag,
+                    expected: entry.check_sum,
+                    actual,
+                });
+            }
+        }
 
-        for entry in sorted_entries {
-            let padding_size = entry.padding();
-            let padding = vec![0u8; padding_size];
-            let padding = Seq::from(padding);
+        if let Some(expected) = loaded.adjustment {
+            let actual = CHECKSUM_MAGIC.wrapping_sub(total);
 
-            self.font_tables[&entry.tag].encode(encoder)?;
-            padding.encode(encoder)?;
+            if actual != expected {
+                mismatches.push(ChecksumMismatch::Adjustment { expected, actual });
+            }
         }
 
-        Ok(())
+        Ok(mismatches)
+    }
+
+    fn encode_table(&self, tag: Tag, table: &FontTable) -> Result<Vec<u8>, EncodeError> {
+        let tables = &self.font_tables;
+
+        match (table, tables.glyf(), tables.hmtx(), tables.vmtx()) {
+            (FontTable::Loca(loca), Ok(glyf), _, _) => encode_to_vec(written_loca(loca, glyf)?),
+            (FontTable::Head(head), Ok(glyf), _, _) => {
+                let mut head = head.clone();
+
+                if let Ok(loca) = tables.loca() {
+                    let format = written_loca(loca, glyf)?.format;
+                    head.index_to_loc_format = (format == LocaFormat::Long) as i16;
+                }
+
+                let mut bytes = encode_to_vec(head)?;
+                clear_checksum_adjustment(&mut bytes);
+                Ok(bytes)
+            }
+            // metric counts follow the metrics table so edits to it stay consistent
+            (FontTable::Hhea(hhea), _, Ok(hmtx), _) => {
+                let mut hhea = hhea.clone();
+                hhea.num_of_long_hor_metrics = hmtx.h_metrics.as_slice().len() as u16;
+                encode_to_vec(hhea)
+            }
+            (FontTable::Vhea(vhea), _, _, Ok(vmtx)) => {
+                let mut vhea = vhea.clone();
+                vhea.num_of_long_ver_metrics = vmtx.v_metrics.as_slice().len() as u16;
+                encode_to_vec(vhea)
+            }
+            _ => {
+                let mut bytes = encode_to_vec(table)?;
+
+                if tag == tags::HEAD {
+                    clear_checksum_adjustment(&mut bytes);
+                }
+
+                Ok(bytes)
+            }
+        }
+    }
+}
+
+impl GetFontTable for Font {
+    fn head(&self) -> Result<&Head, Error> {
+        self.font_tables.head()
+    }
+
+    fn hhea(&self) -> Result<&Hhea, Error> {
+        self.font_tables.hhea()
+    }
+
+    fn maxp(&self) -> Result<&Maxp, Error> {
+        self.font_tables.maxp()
+    }
+
+    fn hmtx(&self) -> Result<&Hmtx, Error> {
+        self.font_tables.hmtx()
+    }
+
+    fn cmap(&self) -> Result<&Cmap, Error> {
+        self.font_tables.cmap()
+    }
+
+    fn loca(&self) -> Result<&Loca, Error> {
+        self.font_tables.loca()
+    }
+
+    fn glyf(&self) -> Result<&Glyf, Error> {
+        self.font_tables.glyf()
+    }
+
+    fn name(&self) -> Result<&Name, Error> {
+        self.font_tables.name()
+    }
+
+    fn post(&self) -> Result<&Post, Error> {
+        self.font_tables.post()
+    }
+
+    fn os2(&self) -> Result<&Os2, Error> {
+        self.font_tables.os2()
+    }
+
+    fn vhea(&self) -> Result<&Vhea, Error> {
+        self.font_tables.vhea()
+    }
+
+    fn vmtx(&self) -> Result<&Vmtx, Error> {
+        self.font_tables.vmtx()
+    }
+}
+
+// a short loca that can no longer address the glyphs is widened, and head is written to match
+fn written_loca(loca: &Loca, glyf: &Glyf) -> Result<Loca, EncodeError> {
+    let offsets = glyf.offsets()?;
+    let format = match loca.format.fits(offsets.as_slice()) {
+        true => loca.format,
+        false => LocaFormat::Long,
+    };
+
+    Ok(Loca { offsets, format })
+}
+
+pub fn write_sfnt(
+    scaler_type: u32,
+    mut tables: BTreeMap<Tag, Vec<u8>>,
+) -> Result<(FontDirectory, Vec<u8>), EncodeError> {
+    if let Some(head) = tables.get_mut(&tags::HEAD) {
+        clear_checksum_adjustment(head);
+    }
+
+    let num_tables = tables.len();
+    let offset_subtable = OffsetSubtable::new(scaler_type, num_tables as u16);
+    let mut data_order = tables.keys().copied().collect::<Vec<_>>();
+    data_order.sort_by(|&a, &b| compare_layout_tags(a, b));
+    let mut offset = OFFSET_SUBTABLE_SIZE + num_tables * TABLE_DIR_ENTRY_SIZE;
+    let mut offsets = BTreeMap::new();
+    let mut data = Vec::new();
+
+    for tag in data_order {
+        let table = &tables[&tag];
+        offsets.insert(tag, offset);
+        data.extend_from_slice(table);
+        data.resize(padded_len(data.len()), 0);
+        offset += padded_len(table.len());
+    }
+
+    let table_directory = tables
+        .iter()
+        .map(|(&tag, table)| TableDirEntry {
+            tag,
+            check_sum: checksum(table),
+            offset: offsets[&tag] as u32,
+            length: table.len() as u32,
+        })
+        .collect();
+    let font_directory = FontDirectory {
+        offset_subtable,
+        table_directory,
+    };
+
+    let mut bytes = encode_to_vec(&font_directory)?;
+    bytes.extend(data);
+
+    let head_length = tables.get(&tags::HEAD).map_or(0, Vec::len);
+    let position = offsets
+        .get(&tags::HEAD)
+        .map(|offset| offset + CHECKSUM_ADJUSTMENT_OFFSET)
+        .filter(|_| head_length >= CHECKSUM_ADJUSTMENT_OFFSET + 4);
+
+    if let Some(position) = position {
+        let adjustment = CHECKSUM_MAGIC.wrapping_sub(checksum(&bytes));
+        bytes[position..position + 4].copy_from_slice(&adjustment.to_be_bytes());
+    }
+
+    Ok((font_directory, bytes))
+}
+
+fn clear_checksum_adjustment(head: &mut [u8]) {
+    let position = CHECKSUM_ADJUSTMENT_OFFSET;
+
+    if let Some(adjustment) = head.get_mut(position..position + 4) {
+        adjustment.fill(0);
     }
 }
@@ -2,6 +2,7 @@ use crate::{
     error::Error,
     table::tags::{Tag, REQUIRED_TAGS},
     utils::{
+        bytes::{read_be, write_be, FixedSize},
         reader::{ReadSeq, TryFromStream},
         types::Seq,
     },
@@ -12,7 +13,9 @@ use std::{
     io::{Read, Seek},
 };
 
-const ALIGNMENT: u32 = 4;
+pub const OFFSET_SUBTABLE_SIZE: usize = 12;
+pub const TABLE_DIR_ENTRY_SIZE: usize = 16;
+pub const ALIGNMENT: usize = 4;
 
 #[derive(Debug, Encode)]
 pub struct FontDirectory {
@@ -71,6 +74,28 @@ pub struct OffsetSubtable {
     pub range_shift: u16,
 }
 
+impl OffsetSubtable {
+    pub fn new(scaler_type: u32, num_tables: u16) -> Self {
+        let entry_selector = match num_tables {
+            0 => 0,
+            n => n.ilog2() as u16,
+        };
+        let entry_size = TABLE_DIR_ENTRY_SIZE as u16;
+        let search_range = (1u16 << entry_selector).wrapping_mul(entry_size);
+        let range_shift = num_tables
+            .wrapping_mul(entry_size)
+            .saturating_sub(search_range);
+
+        Self {
+            scaler_type,
+            num_tables,
+            search_range,
+            entry_selector,
+            range_shift,
+        }
+    }
+}
+
 #[derive(Debug, Encode, Decode)]
 pub struct TableDirEntry {
     pub tag: u32,
@@ -79,10 +104,33 @@ pub struct TableDirEntry {
     pub length: u32,
 }
 
+impl FixedSize for TableDirEntry {
+    const FIXED_SIZE: usize = TABLE_DIR_ENTRY_SIZE;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            tag: read_be(bytes, 0)?,
+            check_sum: read_be(bytes, 4)?,
+            offset: read_be(bytes, 8)?,
+            length: read_be(bytes, 12)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.tag);
+        write_be(bytes, 4, &self.check_sum);
+        write_be(bytes, 8, &self.offset);
+        write_be(bytes, 12, &self.length);
+    }
+}
+
 impl TableDirEntry {
     pub fn padding(&self) -> usize {
-        let remainder = (self.offset + self.length) % ALIGNMENT;
-        let padding = (ALIGNMENT - remainder) % ALIGNMENT;
-        padding as usize
+        let end = self.offset as usize + self.length as usize;
+        padded_len(end) - end
     }
 }
+
+pub fn padded_len(length: usize) -> usize {
+    length.div_ceil(ALIGNMENT) * ALIGNMENT
+}
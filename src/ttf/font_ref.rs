@@ -0,0 +1,184 @@
+use crate::{
+    error::Error,
+    table::{
+        tags::{self, Tag},
+        CmapRef, FontTable, GlyfRef, Head, Hhea, HmtxRef, LocaFormat, LocaRef, Maxp, Vhea, VmtxRef,
+    },
+    ttf::{
+        collection_dir::{TtcHeader, TTC_TAG},
+        font_dir::{FontDirectory, TableDirEntry},
+    },
+    utils::{bincode::decode_from_slice, bytes::read_be, reader::TryFromStream},
+    woff::WOFF_SIGNATURE,
+    woff2::WOFF2_SIGNATURE,
+};
+use bincode::Decode;
+use std::{
+    collections::BTreeMap,
+    io::{Cursor, Seek, SeekFrom},
+};
+
+#[derive(Debug)]
+pub struct FontRef<'a> {
+    data: &'a [u8],
+    font_directory: FontDirectory,
+}
+
+impl<'a> FontRef<'a> {
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        match read_be(data, 0) {
+            Some(TTC_TAG) => Err(Error::UnexpectedCollection),
+            Some(WOFF_SIGNATURE | WOFF2_SIGNATURE) => Err(Error::CompressedFont),
+            _ => Self::from_offset(data, 0),
+        }
+    }
+
+    pub fn from_collection(data: &'a [u8], index: usize) -> Result<Self, Error> {
+        let header = TtcHeader::try_from_stream(&mut Cursor::new(data))?;
+        let offset = header
+            .table_directory_offsets
+            .as_slice()
+            .get(index)
+            .ok_or(Error::InvalidCollection("font index out of range"))?;
+
+        Self::from_offset(data, *offset as u64)
+    }
+
+    fn from_offset(data: &'a [u8], offset: u64) -> Result<Self, Error> {
+        let mut stream = Cursor::new(data);
+        stream.seek(SeekFrom::Start(offset))?;
+        let font_directory = FontDirectory::try_from_stream(&mut stream)?;
+
+        if !font_directory.contains_required_tags() {
+            return Err(Error::MissingRequiredTable);
+        }
+
+        Ok(Self {
+            data,
+            font_directory,
+        })
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn font_directory(&self) -> &FontDirectory {
+        &self.font_directory
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = Tag> + '_ {
+        self.font_directory.table_directory.iter().map(|e| e.tag)
+    }
+
+    pub fn contains_table(&self, tag: Tag) -> bool {
+        self.entry(tag).is_some()
+    }
+
+    pub fn table_data(&self, tag: Tag) -> Result<&'a [u8], Error> {
+        let entry = self.entry(tag).ok_or(Error::MissingTable(tag))?;
+        let start = entry.offset as usize;
+        let end = start + entry.length as usize;
+
+        self.data
+            .get(start..end)
+            .ok_or(Error::TableOutOfBounds(tag))
+    }
+
+    pub fn table(&self, tag: Tag) -> Result<FontTable, Error> {
+        let mut tables = BTreeMap::new();
+
+        for &dependency in table_dependencies(tag) {
+            let table = self.decode_table(dependency, &tables)?;
+            tables.insert(dependency, table);
+        }
+
+        self.decode_table(tag, &tables)
+    }
+
+    pub fn head(&self) -> Result<Head, Error> {
+        self.decode(tags::HEAD)
+    }
+
+    pub fn hhea(&self) -> Result<Hhea, Error> {
+        self.decode(tags::HHEA)
+    }
+
+    pub fn maxp(&self) -> Result<Maxp, Error> {
+        self.decode(tags::MAXP)
+    }
+
+    pub fn cmap(&self) -> Result<CmapRef<'a>, Error> {
+        Ok(CmapRef::new(self.table_data(tags::CMAP)?))
+    }
+
+    pub fn hmtx(&self) -> Result<HmtxRef<'a>, Error> {
+        let num_of_long_hor_metrics = self.hhea()?.num_of_long_hor_metrics as usize;
+        let num_glyphs = self.maxp()?.num_glyphs as usize;
+        let data = self.table_data(tags::HMTX)?;
+
+        Ok(HmtxRef::new(data, num_of_long_hor_metrics, num_glyphs))
+    }
+
+    pub fn vhea(&self) -> Result<Vhea, Error> {
+        self.decode(tags::VHEA)
+    }
+
+    pub fn vmtx(&self) -> Result<VmtxRef<'a>, Error> {
+        let num_of_long_ver_metrics = self.vhea()?.num_of_long_ver_metrics as usize;
+        let num_glyphs = self.maxp()?.num_glyphs as usize;
+        let data = self.table_data(tags::VMTX)?;
+
+        Ok(VmtxRef::new(data, num_of_long_ver_metrics, num_glyphs))
+    }
+
+    pub fn loca(&self) -> Result<LocaRef<'a>, Error> {
+        let format = match self.head()?.index_to_loc_format {
+            0 => LocaFormat::Short,
+            _ => LocaFormat::Long,
+        };
+        let num_glyphs = self.maxp()?.num_glyphs as usize;
+        let data = self.table_data(tags::LOCA)?;
+
+        Ok(LocaRef::new(data, format, num_glyphs))
+    }
+
+    pub fn glyf(&self) -> Result<GlyfRef<'a>, Error> {
+        Ok(GlyfRef::new(self.table_data(tags::GLYF)?, self.loca()?))
+    }
+
+    fn entry(&self, tag: Tag) -> Option<&TableDirEntry> {
+        self.font_directory
+            .table_directory
+            .iter()
+            .find(|e| e.tag == tag)
+    }
+
+    fn decode<T>(&self, tag: Tag) -> Result<T, Error>
+    where
+        T: Decode,
+    {
+        Ok(decode_from_slice(self.table_data(tag)?)?)
+    }
+
+    fn decode_table(
+        &self,
+        tag: Tag,
+        tables: &BTreeMap<Tag, FontTable>,
+    ) -> Result<FontTable, Error> {
+        let entry = self.entry(tag).ok_or(Error::MissingTable(tag))?;
+        self.table_data(tag)?;
+
+        FontTable::try_from_params(entry, tables, &mut Cursor::new(self.data))
+    }
+}
+
+fn table_dependencies(tag: Tag) -> &'static [Tag] {
+    match tag {
+        tags::LOCA => &[tags::HEAD, tags::MAXP],
+        tags::HMTX => &[tags::HHEA, tags::MAXP],
+        tags::VMTX => &[tags::VHEA, tags::MAXP],
+        tags::GLYF => &[tags::HEAD, tags::MAXP, tags::LOCA],
+        _ => &[],
+    }
+}
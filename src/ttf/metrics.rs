@@ -0,0 +1,172 @@
+use crate::{
+    error::Error,
+    sfnt::types::FWord,
+    table::{GetFontTable, LongHorMetric, LongVerMetric},
+    ttf::font::Font,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FontMetrics {
+    pub units_per_em: u16,
+    pub ascent: FWord,
+    pub descent: FWord,
+    pub line_gap: FWord,
+}
+
+impl FontMetrics {
+    pub fn scale(&self, pixel_size: f32) -> Result<f32, Error> {
+        scale(pixel_size, self.units_per_em)
+    }
+
+    pub fn scaled(&self, pixel_size: f32) -> Result<ScaledFontMetrics, Error> {
+        let scale = self.scale(pixel_size)?;
+
+        Ok(ScaledFontMetrics {
+            ascent: f32::from(self.ascent) * scale,
+            descent: f32::from(self.descent) * scale,
+            line_gap: f32::from(self.line_gap) * scale,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaledFontMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    pub line_gap: f32,
+}
+
+impl Font {
+    pub fn font_metrics(&self) -> Result<FontMetrics, Error> {
+        let units_per_em = self.head()?.units_per_em;
+        let typo_metrics = self
+            .os2()
+            .ok()
+            .filter(|t| t.fs_selection().use_typo_metrics());
+
+        let metrics = match typo_metrics {
+            Some(os2) => FontMetrics {
+                units_per_em,
+                ascent: os2.header.s_typo_ascender,
+                descent: os2.header.s_typo_descender,
+                line_gap: os2.header.s_typo_line_gap,
+            },
+            None => {
+                let hhea = self.hhea()?;
+
+                FontMetrics {
+                    units_per_em,
+                    ascent: hhea.ascent,
+                    descent: hhea.descent,
+                    line_gap: hhea.line_gap,
+                }
+            }
+        };
+
+        Ok(metrics)
+    }
+
+    pub fn advance_width(&self, glyph_id: u16) -> Result<u16, Error> {
+        Ok(self.horizontal_metric(glyph_id)?.advance_width)
+    }
+
+    pub fn left_side_bearing(&self, glyph_id: u16) -> Result<FWord, Error> {
+        Ok(self.horizontal_metric(glyph_id)?.left_side_bearing)
+    }
+
+    pub fn right_side_bearing(&self, glyph_id: u16) -> Result<i32, Error> {
+        let metric = self.horizontal_metric(glyph_id)?;
+
+        // empty glyphs have no extent, so the whole advance is bearing
+        let width = self.glyf()?.get(glyph_id).map_or(0, |glyph| {
+            i32::from(glyph.header.x_max) - i32::from(glyph.header.x_min)
+        });
+
+        Ok(i32::from(metric.advance_width) - i32::from(metric.left_side_bearing) - width)
+    }
+
+    pub fn advance_height(&self, glyph_id: u16) -> Result<u16, Error> {
+        Ok(self.vertical_metric(glyph_id)?.advance_height)
+    }
+
+    pub fn top_side_bearing(&self, glyph_id: u16) -> Result<FWord, Error> {
+        Ok(self.vertical_metric(glyph_id)?.top_side_bearing)
+    }
+
+    pub fn scaled_font_metrics(&self, pixel_size: f32) -> Result<ScaledFontMetrics, Error> {
+        self.font_metrics()?.scaled(pixel_size)
+    }
+
+    pub fn scaled_advance_width(&self, glyph_id: u16, pixel_size: f32) -> Result<f32, Error> {
+        let advance = self.advance_width(glyph_id)?;
+        Ok(f32::from(advance) * self.scale(pixel_size)?)
+    }
+
+    pub fn scaled_left_side_bearing(&self, glyph_id: u16, pixel_size: f32) -> Result<f32, Error> {
+        let bearing = self.left_side_bearing(glyph_id)?;
+        Ok(f32::from(bearing) * self.scale(pixel_size)?)
+    }
+
+    pub fn scaled_right_side_bearing(&self, glyph_id: u16, pixel_size: f32) -> Result<f32, Error> {
+        let bearing = self.right_side_bearing(glyph_id)?;
+        Ok(bearing as f32 * self.scale(pixel_size)?)
+    }
+
+    pub fn scaled_advance_height(&self, glyph_id: u16, pixel_size: f32) -> Result<f32, Error> {
+        let advance = self.advance_height(glyph_id)?;
+        Ok(f32::from(advance) * self.scale(pixel_size)?)
+    }
+
+    pub fn scaled_top_side_bearing(&self, glyph_id: u16, pixel_size: f32) -> Result<f32, Error> {
+        let bearing = self.top_side_bearing(glyph_id)?;
+        Ok(f32::from(bearing) * self.scale(pixel_size)?)
+    }
+
+    fn scale(&self, pixel_size: f32) -> Result<f32, Error> {
+        scale(pixel_size, self.head()?.units_per_em)
+    }
+
+    fn horizontal_metric(&self, glyph_id: u16) -> Result<LongHorMetric, Error> {
+        if glyph_id >= self.maxp()?.num_glyphs {
+            return Err(Error::InvalidGlyphId(glyph_id));
+        }
+
+        self.hmtx()?
+            .metric(glyph_id)
+            .ok_or(Error::InvalidGlyphId(glyph_id))
+    }
+
+    fn vertical_metric(&self, glyph_id: u16) -> Result<LongVerMetric, Error> {
+        if glyph_id >= self.maxp()?.num_glyphs {
+            return Err(Error::InvalidGlyphId(glyph_id));
+        }
+
+        if let Ok(vmtx) = self.vmtx() {
+            return vmtx.metric(glyph_id).ok_or(Error::InvalidGlyphId(glyph_id));
+        }
+
+        // without vertical metrics glyphs are stacked on the typographic line height
+        let (ascent, descent) = match self.os2() {
+            Ok(os2) => (os2.header.s_typo_ascender, os2.header.s_typo_descender),
+            Err(_) => {
+                let head = self.head()?;
+                (head.y_max, head.y_min)
+            }
+        };
+        let y_max = self.glyf()?.get(glyph_id).map(|glyph| glyph.header.y_max);
+        let advance_height = i32::from(ascent) - i32::from(descent);
+        let top_side_bearing = y_max.map_or(0, |y_max| i32::from(ascent) - i32::from(y_max));
+
+        Ok(LongVerMetric {
+            advance_height: advance_height.clamp(0, u16::MAX.into()) as u16,
+            top_side_bearing: top_side_bearing.clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+        })
+    }
+}
+
+fn scale(pixel_size: f32, units_per_em: u16) -> Result<f32, Error> {
+    match units_per_em {
+        0 => Err(Error::InvalidUnitsPerEm),
+        units_per_em => Ok(pixel_size / f32::from(units_per_em)),
+    }
+}
@@ -1,2 +1,7 @@
+pub mod checksum;
+pub mod collection;
+pub mod collection_dir;
 pub mod font;
 pub mod font_dir;
+pub mod font_ref;
+pub mod metrics;
@@ -0,0 +1,177 @@
+use crate::utils::types::U24;
+
+pub trait FixedSize: Sized {
+    const FIXED_SIZE: usize;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self>;
+
+    fn to_be_slice(&self, bytes: &mut [u8]);
+
+    fn from_be_vec(bytes: Vec<u8>) -> Vec<Self> {
+        let mut values = Vec::with_capacity(bytes.len() / Self::FIXED_SIZE);
+        let chunks = bytes.chunks_exact(Self::FIXED_SIZE);
+        values.extend(chunks.map_while(Self::from_be_slice));
+        values
+    }
+
+    // conversions run once per value, so they have to inline into the loops that call them
+    #[inline]
+    fn to_be_vec(values: &[Self]) -> Vec<u8> {
+        let mut bytes = vec![0; values.len() * Self::FIXED_SIZE];
+        let chunks = bytes.chunks_exact_mut(Self::FIXED_SIZE);
+        chunks
+            .zip(values)
+            .for_each(|(chunk, value)| value.to_be_slice(chunk));
+        bytes
+    }
+}
+
+impl FixedSize for u8 {
+    const FIXED_SIZE: usize = 1;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        bytes.first().copied()
+    }
+
+    #[inline]
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        bytes[0] = *self;
+    }
+
+    fn from_be_vec(bytes: Vec<u8>) -> Vec<Self> {
+        bytes
+    }
+
+    fn to_be_vec(values: &[Self]) -> Vec<u8> {
+        values.to_vec()
+    }
+}
+
+impl FixedSize for i8 {
+    const FIXED_SIZE: usize = 1;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        bytes.first().map(|&b| b as i8)
+    }
+
+    #[inline]
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        bytes[0] = *self as u8;
+    }
+
+    fn from_be_vec(bytes: Vec<u8>) -> Vec<Self> {
+        bytes.into_iter().map(|b| b as i8).collect()
+    }
+}
+
+impl FixedSize for u16 {
+    const FIXED_SIZE: usize = 2;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(u16::from_be_bytes(*bytes.first_chunk()?))
+    }
+
+    #[inline]
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        bytes[..Self::FIXED_SIZE].copy_from_slice(&self.to_be_bytes());
+    }
+
+    fn from_be_vec(bytes: Vec<u8>) -> Vec<Self> {
+        convert_chunks(&bytes, u16::from_be_bytes)
+    }
+}
+
+impl FixedSize for i16 {
+    const FIXED_SIZE: usize = 2;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(i16::from_be_bytes(*bytes.first_chunk()?))
+    }
+
+    #[inline]
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        bytes[..Self::FIXED_SIZE].copy_from_slice(&self.to_be_bytes());
+    }
+
+    fn from_be_vec(bytes: Vec<u8>) -> Vec<Self> {
+        convert_chunks(&bytes, i16::from_be_bytes)
+    }
+}
+
+impl FixedSize for U24 {
+    const FIXED_SIZE: usize = 3;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        let [a, b, c] = *bytes.first_chunk()?;
+        Some(U24(u32::from_be_bytes([0, a, b, c])))
+    }
+
+    #[inline]
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        bytes[..Self::FIXED_SIZE].copy_from_slice(&self.0.to_be_bytes()[1..]);
+    }
+}
+
+impl FixedSize for u32 {
+    const FIXED_SIZE: usize = 4;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(u32::from_be_bytes(*bytes.first_chunk()?))
+    }
+
+    #[inline]
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        bytes[..Self::FIXED_SIZE].copy_from_slice(&self.to_be_bytes());
+    }
+
+    fn from_be_vec(bytes: Vec<u8>) -> Vec<Self> {
+        convert_chunks(&bytes, u32::from_be_bytes)
+    }
+}
+
+// filling a preallocated buffer lets the conversion vectorize, collecting from chunks does not
+fn convert_chunks<T, const N: usize>(bytes: &[u8], convert: fn([u8; N]) -> T) -> Vec<T>
+where
+    T: Default + Clone,
+{
+    let mut values = vec![T::default(); bytes.len() / N];
+
+    for (value, chunk) in values.iter_mut().zip(bytes.chunks_exact(N)) {
+        *value = convert(*chunk.first_chunk().unwrap_or(&[0; N]));
+    }
+
+    values
+}
+
+pub fn read_be<T>(data: &[u8], offset: usize) -> Option<T>
+where
+    T: FixedSize,
+{
+    T::from_be_slice(data.get(offset..)?)
+}
+
+#[inline]
+pub fn write_be<T>(data: &mut [u8], offset: usize, value: &T)
+where
+    T: FixedSize,
+{
+    value.to_be_slice(&mut data[offset..offset + T::FIXED_SIZE]);
+}
+
+pub fn partition_point<F>(length: usize, mut pred: F) -> usize
+where
+    F: FnMut(usize) -> bool,
+{
+    let (mut low, mut high) = (0, length);
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+
+        match pred(middle) {
+            true => low = middle + 1,
+            false => high = middle,
+        }
+    }
+
+    low
+}
@@ -1,4 +1,6 @@
 pub mod bincode;
 pub mod bitflag;
+pub mod bytes;
+pub mod encoding;
 pub mod reader;
 pub mod types;
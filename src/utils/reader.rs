@@ -1,15 +1,11 @@
 use crate::{
     error::Error,
-    utils::{
-        bincode::{decode_from_reader, decode_from_slice},
-        types::Seq,
-    },
+    utils::{bincode::decode_from_reader, bytes::FixedSize, types::Seq},
 };
 use bincode::Decode;
-use std::{
-    io::{Read, Seek},
-    mem,
-};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+
+const MAX_PREALLOCATION: usize = 1 << 16;
 
 pub trait TryFromStream: Sized {
     fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
@@ -29,7 +25,7 @@ where
 pub trait ReadSeq {
     fn read_seq<T>(&mut self, length: usize) -> Result<Seq<T>, Error>
     where
-        T: Decode;
+        T: FixedSize;
 }
 
 impl<T> ReadSeq for T
@@ -38,18 +34,100 @@ where
 {
     fn read_seq<U>(&mut self, length: usize) -> Result<Seq<U>, Error>
     where
-        U: Decode,
+        U: FixedSize,
     {
-        let size = mem::size_of::<U>();
-        let mut buffer = vec![0; length * size];
+        let size = length.saturating_mul(U::FIXED_SIZE);
+
+        // counts come from the font itself, so large buffers only grow as data actually arrives
+        let buffer = match size <= MAX_PREALLOCATION {
+            true => {
+                let mut buffer = vec![0; size];
+                self.read_exact(&mut buffer)?;
+                buffer
+            }
+            false => {
+                let mut buffer = Vec::with_capacity(MAX_PREALLOCATION);
+                self.take(size as u64).read_to_end(&mut buffer)?;
+                buffer
+            }
+        };
+
+        let values = match buffer.len() == size {
+            true => U::from_be_vec(buffer),
+            false => Vec::new(),
+        };
+
+        if values.len() != length {
+            return Err(Error::IOError(ErrorKind::UnexpectedEof.into()));
+        }
+
+        Ok(values.into())
+    }
+}
+
+pub struct BoundedStream<'a, T> {
+    stream: &'a mut T,
+    position: u64,
+    end: u64,
+}
+
+impl<'a, T> BoundedStream<'a, T>
+where
+    T: Read + Seek,
+{
+    pub fn new(stream: &'a mut T, end: u64) -> Result<Self, Error> {
+        let position = stream.stream_position()?;
+
+        Ok(Self {
+            stream,
+            position,
+            end,
+        })
+    }
+}
 
-        self.read_exact(&mut buffer)?;
+impl<T> Read for BoundedStream<'_, T>
+where
+    T: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.end.saturating_sub(self.position);
+        let length = remaining.min(buf.len() as u64) as usize;
+        let read = self.stream.read(&mut buf[..length])?;
+        self.position += read as u64;
+        Ok(read)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        if self.end.saturating_sub(self.position) < buf.len() as u64 {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
+        self.stream.read_exact(buf)?;
+        self.position += buf.len() as u64;
+        Ok(())
+    }
+}
 
-        let results = buffer
-            .chunks_exact(size)
-            .map(decode_from_slice)
-            .collect::<Result<_, _>>()?;
+impl<T> Seek for BoundedStream<'_, T>
+where
+    T: Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.stream.seek(pos)?;
+        Ok(self.position)
+    }
+}
+
+pub fn read_bytes<T>(length: usize, stream: &mut T) -> Result<Vec<u8>, Error>
+where
+    T: Read,
+{
+    let mut bytes = Vec::new();
+    stream.take(length as u64).read_to_end(&mut bytes)?;
 
-        Ok(results)
+    match bytes.len() == length {
+        true => Ok(bytes),
+        false => Err(Error::IOError(ErrorKind::UnexpectedEof.into())),
     }
 }
@@ -1,10 +1,22 @@
-use bincode::{enc::Encoder, error::EncodeError, Encode};
+use crate::utils::bytes::FixedSize;
+use bincode::{
+    de::Decoder,
+    enc::{write::Writer, Encoder},
+    error::{DecodeError, EncodeError},
+    impl_borrow_decode, Decode, Encode,
+};
 
 pub type Padding<const N: usize> = [u8; N];
 
 #[derive(Debug)]
 pub struct Seq<T>(Vec<T>);
 
+impl<T> Default for Seq<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
 impl<T> Seq<T> {
     pub fn as_slice(&self) -> &[T] {
         &self.0
@@ -21,10 +33,26 @@ impl<T> Seq<T> {
 
 impl<T> Encode for Seq<T>
 where
-    T: Encode,
+    T: EncodeSeq,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        self.0.iter().try_for_each(|item| item.encode(encoder))
+        T::encode_seq(&self.0, encoder)
+    }
+}
+
+pub trait EncodeSeq: Encode + Sized {
+    fn encode_seq<E: Encoder>(values: &[Self], encoder: &mut E) -> Result<(), EncodeError> {
+        values.iter().try_for_each(|value| value.encode(encoder))
+    }
+}
+
+// fixed size values are converted together and handed to the writer in one go
+impl<T> EncodeSeq for T
+where
+    T: FixedSize + Encode,
+{
+    fn encode_seq<E: Encoder>(values: &[Self], encoder: &mut E) -> Result<(), EncodeError> {
+        encoder.writer().write(&T::to_be_vec(values))
     }
 }
 
@@ -62,6 +90,13 @@ impl<T> Opt<T> {
             Opt::None => None,
         }
     }
+
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Opt::Some(value) => Some(value),
+            Opt::None => None,
+        }
+    }
 }
 
 impl<T> From<Option<T>> for Opt<T> {
@@ -84,3 +119,22 @@ where
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct U24(pub u32);
+
+impl Encode for U24 {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let [_, bytes @ ..] = self.0.to_be_bytes();
+        bytes.encode(encoder)
+    }
+}
+
+impl Decode for U24 {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let [a, b, c] = <[u8; 3]>::decode(decoder)?;
+        Ok(Self(u32::from_be_bytes([0, a, b, c])))
+    }
+}
+
+impl_borrow_decode!(U24);
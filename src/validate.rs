@@ -0,0 +1,604 @@
+use crate::{
+    error::Error,
+    table::{
+        glyph::{glyph_stats, Glyph, GlyphData, GlyphResolver, MAX_COMPONENT_DEPTH},
+        tags::{self, tag_to_string, Tag},
+        CmapSubtable, Glyf, Head, Hhea, LocaFormat, LocaRef, Maxp, Vhea,
+    },
+    ttf::{
+        collection_dir::TTC_TAG,
+        font_dir::{padded_len, ALIGNMENT, OFFSET_SUBTABLE_SIZE, TABLE_DIR_ENTRY_SIZE},
+        font_ref::FontRef,
+    },
+    utils::{bytes::read_be, reader::TryFromStream, types::Opt},
+};
+use std::{collections::BTreeMap, io::Cursor};
+
+const MAGIC_NUMBER: u32 = 0x5F0F3CF5;
+const MAXP_VERSION_1: u32 = 0x00010000;
+const ENCODING_RECORD_SIZE: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub severity: Severity,
+    pub tag: Tag,
+    pub offset: u64,
+    pub glyph_id: Option<u16>,
+    pub description: String,
+}
+
+pub fn validate(font: &FontRef) -> Vec<Issue> {
+    let mut validator = Validator {
+        font,
+        issues: Vec::new(),
+    };
+
+    validator.check_directory();
+
+    let head = validator.decode(tags::HEAD, font.head());
+    let hhea = validator.decode(tags::HHEA, font.hhea());
+    let maxp = validator.decode(tags::MAXP, font.maxp());
+
+    if let Some(head) = &head {
+        validator.check_head(head);
+    }
+
+    if let (Some(hhea), Some(maxp)) = (&hhea, &maxp) {
+        validator.check_hmtx(hhea, maxp);
+    }
+
+    if font.contains_table(tags::VHEA) {
+        let vhea = validator.decode(tags::VHEA, font.vhea());
+
+        if let (Some(vhea), Some(maxp)) = (&vhea, &maxp) {
+            validator.check_vmtx(vhea, maxp);
+        }
+    } else if font.contains_table(tags::VMTX) {
+        let offset = validator.table_offset(tags::VMTX);
+        validator.error(tags::VMTX, offset, None, "vmtx table without a vhea table");
+    }
+
+    validator.check_cmap();
+
+    if let (Some(head), Some(maxp)) = (head, maxp) {
+        validator.check_glyphs(&head, maxp);
+    }
+
+    validator.issues
+}
+
+struct Validator<'a> {
+    font: &'a FontRef<'a>,
+    issues: Vec<Issue>,
+}
+
+impl Validator<'_> {
+    fn check_directory(&mut self) {
+        let data = self.font.data();
+        let entries = self.font.font_directory().table_directory.as_slice();
+        let directory_end = (OFFSET_SUBTABLE_SIZE + entries.len() * TABLE_DIR_ENTRY_SIZE) as u64;
+        let standalone = read_be::<u32>(data, 0) != Some(TTC_TAG);
+        let mut counts = BTreeMap::<Tag, usize>::new();
+
+        for entry in entries {
+            let offset = entry.offset as u64;
+            *counts.entry(entry.tag).or_default() += 1;
+
+            if offset + entry.length as u64 > data.len() as u64 {
+                let description = "table extends past the end of the font data";
+                self.error(entry.tag, offset, None, description);
+            }
+
+            if standalone && offset < directory_end {
+                let description = "table overlaps the table directory";
+                self.error(entry.tag, offset, None, description);
+            }
+
+            if !offset.is_multiple_of(ALIGNMENT as u64) {
+                let description = "table offset is not 4-byte aligned";
+                self.warning(entry.tag, offset, None, description);
+            }
+        }
+
+        for (tag, count) in counts.into_iter().filter(|&(_, c)| c > 1) {
+            let description = format!("table appears {count} times in the directory");
+            self.error(tag, 0, None, description);
+        }
+
+        let mut sorted = entries.iter().filter(|e| e.length > 0).collect::<Vec<_>>();
+        sorted.sort_by_key(|e| e.offset);
+        let mut furthest: Option<(Tag, u64)> = None;
+
+        for entry in sorted {
+            let offset = entry.offset as u64;
+            let end = offset + entry.length as u64;
+
+            match furthest {
+                Some((tag, furthest_end)) if offset < furthest_end => {
+                    let description = format!("table overlaps '{}'", tag_to_string(tag));
+                    self.error(entry.tag, offset, None, description);
+
+                    if end > furthest_end {
+                        furthest = Some((entry.tag, end));
+                    }
+                }
+                _ => furthest = Some((entry.tag, end)),
+            }
+        }
+    }
+
+    fn check_head(&mut self, head: &Head) {
+        let offset = self.table_offset(tags::HEAD);
+
+        if head.magic_number != MAGIC_NUMBER {
+            let description = format!("magic number is {:#010x}", head.magic_number);
+            self.error(tags::HEAD, offset + 12, None, description);
+        }
+
+        if !matches!(head.index_to_loc_format, 0 | 1) {
+            let description = format!("unknown indexToLocFormat {}", head.index_to_loc_format);
+            self.error(tags::HEAD, offset + 50, None, description);
+        }
+    }
+
+    fn check_hmtx(&mut self, hhea: &Hhea, maxp: &Maxp) {
+        let count = hhea.num_of_long_hor_metrics;
+        self.check_metrics(tags::HHEA, tags::HMTX, "hhea.numberOfHMetrics", count, maxp);
+    }
+
+    fn check_vmtx(&mut self, vhea: &Vhea, maxp: &Maxp) {
+        let count = vhea.num_of_long_ver_metrics;
+        self.check_metrics(
+            tags::VHEA,
+            tags::VMTX,
+            "vhea.numOfLongVerMetrics",
+            count,
+            maxp,
+        );
+    }
+
+    fn check_metrics(&mut self, header: Tag, table: Tag, field: &str, count: u16, maxp: &Maxp) {
+        let num_of_long_metrics = count as usize;
+        let num_glyphs = maxp.num_glyphs as usize;
+        let header_offset = self.table_offset(header) + 34;
+        let name = tag_to_string(table);
+
+        let Some(remainder) = num_glyphs.checked_sub(num_of_long_metrics) else {
+            let description = format!("{field} exceeds maxp.numGlyphs");
+            return self.error(header, header_offset, None, description);
+        };
+
+        if num_of_long_metrics == 0 && num_glyphs > 0 {
+            let description = format!("{field} is zero");
+            self.error(header, header_offset, None, description);
+        }
+
+        let Ok(data) = self.font.table_data(table) else {
+            return;
+        };
+
+        let offset = self.table_offset(table);
+        let required = num_of_long_metrics * 4 + remainder * 2;
+
+        if data.len() < required {
+            let description = format!("{name} table is shorter than its metrics require");
+            self.error(table, offset, None, description);
+        } else if data.len() > required {
+            let description = format!("{name} table has {} trailing bytes", data.len() - required);
+            self.warning(table, offset, None, description);
+        }
+    }
+
+    fn check_cmap(&mut self) {
+        let Ok(data) = self.font.table_data(tags::CMAP) else {
+            return;
+        };
+
+        let table_offset = self.table_offset(tags::CMAP);
+        let number_subtables = read_be::<u16>(data, 2).unwrap_or_default() as usize;
+        let mut offsets = Vec::new();
+
+        for i in 0..number_subtables {
+            let record = 4 + i * ENCODING_RECORD_SIZE;
+
+            let Some(offset) = read_be::<u32>(data, record + 4) else {
+                let description = "encoding records extend past the end of the table";
+                return self.error(tags::CMAP, table_offset + record as u64, None, description);
+            };
+
+            if offsets.contains(&offset) {
+                continue;
+            }
+
+            offsets.push(offset);
+            let subtable_offset = table_offset + offset as u64;
+
+            let Some(subtable) = data.get(offset as usize..).filter(|s| !s.is_empty()) else {
+                let position = table_offset + (record + 4) as u64;
+                let description = "subtable offset beyond cmap length";
+                self.error(tags::CMAP, position, None, description);
+                continue;
+            };
+
+            match CmapSubtable::try_from_stream(&mut Cursor::new(subtable)) {
+                Ok(subtable) => self.check_cmap_subtable(subtable_offset, &subtable),
+                Err(e) => {
+                    let description = format!("subtable could not be parsed: {e}");
+                    self.error(tags::CMAP, subtable_offset, None, description);
+                }
+            }
+        }
+    }
+
+    fn check_cmap_subtable(&mut self, offset: u64, subtable: &CmapSubtable) {
+        match subtable {
+            CmapSubtable::Format4(format4) => {
+                let end_code = format4.end_code.as_slice();
+                let start_code = format4.start_code.as_slice();
+                let seg_count_x2 = format4.seg_count_x2 as u64;
+
+                if !seg_count_x2.is_multiple_of(2) {
+                    self.error(tags::CMAP, offset + 6, None, "segCountX2 is odd");
+                }
+
+                for (i, (&start, &end)) in start_code.iter().zip(end_code).enumerate() {
+                    let end_position = offset + 14 + i as u64 * 2;
+                    let start_position = end_position + seg_count_x2 + 2;
+
+                    if start > end {
+                        let description = format!("segment {i} starts after it ends");
+                        self.error(tags::CMAP, start_position, None, description);
+                    }
+
+                    match i.checked_sub(1).map(|p| end_code[p]) {
+                        Some(previous) if end <= previous => {
+                            let description = "segment end codes are not sorted";
+                            self.error(tags::CMAP, end_position, None, description);
+                        }
+                        Some(previous) if start <= previous => {
+                            let description = format!("segment {i} overlaps the previous segment");
+                            self.error(tags::CMAP, start_position, None, description);
+                        }
+                        _ => {}
+                    }
+                }
+
+                if end_code.last() != Some(&0xFFFF) {
+                    let position = offset + 14 + seg_count_x2.saturating_sub(2);
+                    let description = "last segment does not end with 0xFFFF";
+                    self.error(tags::CMAP, position, None, description);
+                }
+            }
+            CmapSubtable::Format12(format12) => {
+                let groups = format12.groups.iter();
+                self.check_groups(offset, groups.map(|g| (g.start_char_code, g.end_char_code)));
+            }
+            CmapSubtable::Format13(format13) => {
+                let groups = format13.groups.iter();
+                self.check_groups(offset, groups.map(|g| (g.start_char_code, g.end_char_code)));
+            }
+            _ => {}
+        }
+    }
+
+    fn check_groups<I>(&mut self, offset: u64, groups: I)
+    where
+        I: Iterator<Item = (u32, u32)>,
+    {
+        let mut previous_end = None;
+
+        for (i, (start, end)) in groups.enumerate() {
+            let position = offset + 16 + i as u64 * 12;
+
+            if start > end {
+                let description = format!("group {i} starts after it ends");
+                self.error(tags::CMAP, position, None, description);
+            }
+
+            if previous_end.is_some_and(|previous| start <= previous) {
+                let description = format!("group {i} is not sorted or overlaps the previous group");
+                self.error(tags::CMAP, position, None, description);
+            }
+
+            previous_end = Some(end);
+        }
+    }
+
+    fn check_glyphs(&mut self, head: &Head, maxp: Maxp) {
+        let format = match head.index_to_loc_format {
+            0 => LocaFormat::Short,
+            1 => LocaFormat::Long,
+            _ => return,
+        };
+
+        let (Ok(loca), Ok(glyf)) = (
+            self.font.table_data(tags::LOCA),
+            self.font.table_data(tags::GLYF),
+        ) else {
+            return;
+        };
+
+        let num_glyphs = maxp.num_glyphs as usize;
+        let loca_offset = self.table_offset(tags::LOCA);
+        let glyf_offset = self.table_offset(tags::GLYF);
+        let loca = LocaRef::new(loca, format, num_glyphs);
+
+        if loca.len() < num_glyphs + 1 {
+            let description = "loca table is shorter than maxp.numGlyphs + 1 offsets";
+            self.error(tags::LOCA, loca_offset, None, description);
+        }
+
+        let mut glyphs = Vec::with_capacity(num_glyphs);
+        let mut starts = Vec::with_capacity(num_glyphs);
+
+        for glyph_id in 0..loca.len().saturating_sub(1) {
+            let start = loca.offset(glyph_id).unwrap_or_default() as usize;
+            let end = loca.offset(glyph_id + 1).unwrap_or_default() as usize;
+            let entry = loca_offset + ((glyph_id + 1) * format.size()) as u64;
+            let id = Some(glyph_id as u16);
+            starts.push(glyf_offset + start as u64);
+
+            if end < start {
+                let description = "loca offsets are not in ascending order";
+                self.error(tags::LOCA, entry, id, description);
+                glyphs.push(None);
+                continue;
+            }
+
+            if end > padded_len(glyf.len()) {
+                self.error(tags::LOCA, entry, id, "loca offset beyond glyf length");
+                glyphs.push(None);
+                continue;
+            }
+
+            if start == end {
+                glyphs.push(None);
+                continue;
+            }
+
+            let data = glyf.get(start..end.min(glyf.len())).unwrap_or_default();
+
+            match Glyph::try_from_stream(&mut Cursor::new(data)) {
+                Ok(glyph) => glyphs.push(Some(glyph)),
+                Err(e) => {
+                    let description = format!("glyph could not be parsed: {e}");
+                    self.error(tags::GLYF, starts[glyph_id], id, description);
+                    glyphs.push(None);
+                }
+            }
+        }
+
+        let glyf = Glyf::new(glyphs.into_iter().map(Into::into).collect());
+
+        self.check_outlines(&glyf, &starts, maxp);
+    }
+
+    fn check_outlines(&mut self, glyf: &Glyf, starts: &[u64], maxp: Maxp) {
+        let num_glyphs = maxp.num_glyphs as usize;
+        let mut maxima = Maxima::default();
+        let mut memo = vec![None; glyf.num_glyphs()];
+        let limits = Maxp {
+            max_component_depth: MAX_COMPONENT_DEPTH,
+            ..maxp
+        };
+        let resolver = GlyphResolver::new(glyf, &limits);
+
+        for (glyph_id, glyph) in glyf.glyphs.iter().enumerate() {
+            let Opt::Some(glyph) = glyph else {
+                continue;
+            };
+
+            let id = glyph_id as u16;
+            let offset = starts[glyph_id];
+            let header = &glyph.header;
+            let header_bounds = [header.x_min, header.y_min, header.x_max, header.y_max];
+
+            match &glyph.data {
+                GlyphData::Simple(simple) => {
+                    let end_pts = simple.end_pts_of_contours.as_slice();
+                    let points = end_pts.last().map_or(0, |&p| p as u32 + 1);
+                    let instructions = simple.instruction_length as u32;
+
+                    maxima.points.update(points, id);
+                    maxima.contours.update(end_pts.len() as u32, id);
+                    maxima.instructions.update(instructions, id);
+
+                    if end_pts.windows(2).any(|w| w[1] <= w[0]) {
+                        let description = "contour end points are not in ascending order";
+                        self.error(tags::GLYF, offset, Some(id), description);
+                    } else if !end_pts.is_empty() && simple.bounds() != header_bounds {
+                        let description = "bounding box does not match the outline";
+                        self.warning(tags::GLYF, offset, Some(id), description);
+                    }
+                }
+                GlyphData::Compound(compound) => {
+                    let components = compound.components.as_slice();
+                    let instructions = compound.instruction_length.as_option();
+                    let instructions = instructions.map_or(0, |&l| l as u32);
+
+                    maxima.elements.update(components.len() as u32, id);
+                    maxima.instructions.update(instructions, id);
+
+                    let out_of_range = components
+                        .iter()
+                        .filter(|c| c.glyph_index as usize >= num_glyphs)
+                        .map(|c| c.glyph_index)
+                        .collect::<Vec<_>>();
+
+                    for &component in &out_of_range {
+                        let description = format!(
+                            "component references glyph {component}, which is out of range"
+                        );
+                        self.error(tags::GLYF, offset, Some(id), description);
+                    }
+
+                    if !out_of_range.is_empty() {
+                        continue;
+                    }
+
+                    let stats = match glyph_stats(glyf, id, &mut memo, &mut Vec::new()) {
+                        Ok(stats) => stats,
+                        Err(e) => {
+                            self.error(tags::GLYF, offset, Some(id), e.to_string());
+                            continue;
+                        }
+                    };
+
+                    maxima.component_points.update(stats.points, id);
+                    maxima.component_contours.update(stats.contours, id);
+                    maxima.component_depth.update(stats.depth as u32, id);
+
+                    if stats.points > u16::MAX as u32 {
+                        continue;
+                    }
+
+                    match resolver.resolve(id).map(|r| r.bounds()) {
+                        Ok(Some(bounds)) => {
+                            let matches = header_bounds
+                                .iter()
+                                .zip(bounds)
+                                .all(|(&h, b)| (h as f32 - b).abs() <= 1.0);
+
+                            if !matches {
+                                let description = "bounding box does not match the outline";
+                                self.warning(tags::GLYF, offset, Some(id), description);
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => self.error(tags::GLYF, offset, Some(id), e.to_string()),
+                    }
+                }
+            }
+        }
+
+        if maxp.version < MAXP_VERSION_1 {
+            return;
+        }
+
+        let maxp_offset = self.table_offset(tags::MAXP);
+        let checks = [
+            ("maxPoints", 6, maxp.max_points, maxima.points),
+            ("maxContours", 8, maxp.max_contours, maxima.contours),
+            (
+                "maxCompositePoints",
+                10,
+                maxp.max_component_points,
+                maxima.component_points,
+            ),
+            (
+                "maxCompositeContours",
+                12,
+                maxp.max_component_contours,
+                maxima.component_contours,
+            ),
+            (
+                "maxSizeOfInstructions",
+                26,
+                maxp.max_size_of_instructions,
+                maxima.instructions,
+            ),
+            (
+                "maxComponentElements",
+                28,
+                maxp.max_component_elements,
+                maxima.elements,
+            ),
+            (
+                "maxComponentDepth",
+                30,
+                maxp.max_component_depth,
+                maxima.component_depth,
+            ),
+        ];
+
+        for (field, position, limit, maximum) in checks {
+            if let Some((value, glyph_id)) = maximum.0.filter(|&(v, _)| v > limit as u32) {
+                let position = maxp_offset + position;
+                let description =
+                    format!("maxp.{field} is {limit} but glyph {glyph_id} needs {value}");
+                self.error(tags::MAXP, position, Some(glyph_id), description);
+            }
+        }
+    }
+
+    fn decode<T>(&mut self, tag: Tag, result: Result<T, Error>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            // already reported by the directory checks
+            Err(Error::TableOutOfBounds(_)) => None,
+            Err(e) => {
+                let description = format!("table could not be read: {e}");
+                self.error(tag, self.table_offset(tag), None, description);
+                None
+            }
+        }
+    }
+
+    fn table_offset(&self, tag: Tag) -> u64 {
+        self.font
+            .font_directory()
+            .table_directory
+            .iter()
+            .find(|e| e.tag == tag)
+            .map_or(0, |e| e.offset as u64)
+    }
+
+    fn error<D>(&mut self, tag: Tag, offset: u64, glyph_id: Option<u16>, description: D)
+    where
+        D: Into<String>,
+    {
+        self.push(Severity::Error, tag, offset, glyph_id, description.into());
+    }
+
+    fn warning<D>(&mut self, tag: Tag, offset: u64, glyph_id: Option<u16>, description: D)
+    where
+        D: Into<String>,
+    {
+        self.push(Severity::Warning, tag, offset, glyph_id, description.into());
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        tag: Tag,
+        offset: u64,
+        glyph_id: Option<u16>,
+        description: String,
+    ) {
+        self.issues.push(Issue {
+            severity,
+            tag,
+            offset,
+            glyph_id,
+            description,
+        });
+    }
+}
+
+#[derive(Debug, Default)]
+struct Maximum(Option<(u32, u16)>);
+
+impl Maximum {
+    fn update(&mut self, value: u32, glyph_id: u16) {
+        if self.0.is_none_or(|(maximum, _)| value > maximum) {
+            self.0 = Some((value, glyph_id));
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Maxima {
+    points: Maximum,
+    contours: Maximum,
+    component_points: Maximum,
+    component_contours: Maximum,
+    instructions: Maximum,
+    elements: Maximum,
+    component_depth: Maximum,
+}
@@ -0,0 +1,281 @@
+pub mod woff_dir;
+
+use crate::{
+    error::Error,
+    ttf::{
+        font::{Font, MAX_SFNT_SIZE},
+        font_dir::{
+            padded_len, OffsetSubtable, TableDirEntry, OFFSET_SUBTABLE_SIZE, TABLE_DIR_ENTRY_SIZE,
+        },
+    },
+    utils::{
+        bincode::encode_to_vec,
+        reader::{read_bytes, TryFromStream},
+        types::Seq,
+    },
+    woff::woff_dir::{WoffDirectory, WoffHeader, WoffTableDirEntry},
+};
+use bincode::{
+    enc::{write::Writer, Encoder},
+    error::EncodeError,
+    Encode,
+};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+pub const WOFF_SIGNATURE: u32 = 0x774F4646;
+
+const WOFF_HEADER_SIZE: usize = 44;
+const WOFF_TABLE_DIR_ENTRY_SIZE: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WoffOptions {
+    pub compression_level: u32,
+    pub pad_end: bool,
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Default for WoffOptions {
+    fn default() -> Self {
+        Self {
+            compression_level: Compression::default().level(),
+            pad_end: true,
+            major_version: 0,
+            minor_version: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Woff {
+    pub font: Font,
+    pub metadata: Option<Vec<u8>>,
+    pub private_data: Option<Vec<u8>>,
+    pub options: WoffOptions,
+}
+
+impl Woff {
+    pub fn new(font: Font) -> Self {
+        Self {
+            font,
+            metadata: None,
+            private_data: None,
+            options: WoffOptions::default(),
+        }
+    }
+}
+
+impl TryFromStream for Woff {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let start = stream.stream_position()?;
+        let directory = WoffDirectory::try_from_stream(stream)?;
+        let header = &directory.header;
+        let entries = directory.get_sorted_table_entries();
+        let num_tables = entries.len();
+        let decoded_size = entries
+            .iter()
+            .map(|e| e.orig_length as u64)
+            .chain([header.meta_orig_length as u64])
+            .sum::<u64>();
+
+        if decoded_size > MAX_SFNT_SIZE {
+            return Err(Error::DataTooLarge(decoded_size));
+        }
+
+        let mut offset = OFFSET_SUBTABLE_SIZE + num_tables * TABLE_DIR_ENTRY_SIZE;
+        let mut table_directory = Vec::new();
+        let mut data = Vec::new();
+
+        for entry in entries {
+            stream.seek(SeekFrom::Start(start + entry.offset as u64))?;
+            let table = read_table(entry, stream)?;
+
+            table_directory.push(TableDirEntry {
+                tag: entry.tag,
+                check_sum: entry.orig_checksum,
+                offset: offset as u32,
+                length: table.len() as u32,
+            });
+
+            data.extend(&table);
+            data.resize(padded_len(data.len()), 0);
+            offset += padded_len(table.len());
+        }
+
+        table_directory.sort_by_key(|e| e.tag);
+
+        let offset_subtable = OffsetSubtable::new(header.flavor, num_tables as u16);
+        let mut sfnt = encode_to_vec(offset_subtable)?;
+
+        for entry in table_directory {
+            sfnt.extend(encode_to_vec(entry)?);
+        }
+
+        sfnt.extend(data);
+
+        let metadata = match header.meta_length {
+            0 => None,
+            length => {
+                stream.seek(SeekFrom::Start(start + header.meta_offset as u64))?;
+                let compressed = read_bytes(length as usize, stream)?;
+                let metadata = inflate(&compressed, header.meta_orig_length)?;
+
+                if metadata.len() != header.meta_orig_length as usize {
+                    return Err(Error::InvalidWoffMetadata);
+                }
+
+                Some(metadata)
+            }
+        };
+
+        let private_data = match header.priv_length {
+            0 => None,
+            length => {
+                stream.seek(SeekFrom::Start(start + header.priv_offset as u64))?;
+                Some(read_bytes(length as usize, stream)?)
+            }
+        };
+
+        Ok(Self {
+            font: Font::from_sfnt_stream(&mut Cursor::new(sfnt))?,
+            metadata,
+            private_data,
+            options: WoffOptions {
+                major_version: header.major_version,
+                minor_version: header.minor_version,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+impl Encode for Woff {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let (font_directory, sfnt) = self.font.encode_sfnt()?;
+        let compression = Compression::new(self.options.compression_level);
+        let mut entries = font_directory.table_directory.iter().collect::<Vec<_>>();
+        let num_tables = entries.len();
+        let header_size = WOFF_HEADER_SIZE + num_tables * WOFF_TABLE_DIR_ENTRY_SIZE;
+        let mut table_directory = Vec::new();
+        let mut data = Vec::new();
+
+        entries.sort_by_key(|e| e.offset);
+
+        for entry in entries {
+            let start = entry.offset as usize;
+            let table = &sfnt[start..start + entry.length as usize];
+            let compressed = deflate(table, compression)?;
+            let table = match compressed.len() < table.len() {
+                true => compressed.as_slice(),
+                false => table,
+            };
+
+            data.resize(padded_len(data.len()), 0);
+            table_directory.push(WoffTableDirEntry {
+                tag: entry.tag,
+                offset: (header_size + data.len()) as u32,
+                comp_length: table.len() as u32,
+                orig_length: entry.length,
+                orig_checksum: entry.check_sum,
+            });
+            data.extend(table);
+        }
+
+        table_directory.sort_by_key(|e| e.tag);
+
+        let (meta_offset, meta_length, meta_orig_length) = match &self.metadata {
+            Some(metadata) => {
+                let compressed = deflate(metadata, compression)?;
+                data.resize(padded_len(data.len()), 0);
+                let offset = header_size + data.len();
+                data.extend(&compressed);
+                (offset, compressed.len(), metadata.len())
+            }
+            None => (0, 0, 0),
+        };
+
+        let (priv_offset, priv_length) = match &self.private_data {
+            Some(private_data) => {
+                data.resize(padded_len(data.len()), 0);
+                let offset = header_size + data.len();
+                data.extend(private_data);
+                (offset, private_data.len())
+            }
+            None => (0, 0),
+        };
+
+        if self.options.pad_end {
+            data.resize(padded_len(data.len()), 0);
+        }
+
+        let header = WoffHeader {
+            signature: WOFF_SIGNATURE,
+            flavor: font_directory.offset_subtable.scaler_type,
+            length: (header_size + data.len()) as u32,
+            num_tables: num_tables as u16,
+            _reserved: 0,
+            total_sfnt_size: sfnt_size(&font_directory.table_directory) as u32,
+            major_version: self.options.major_version,
+            minor_version: self.options.minor_version,
+            meta_offset: meta_offset as u32,
+            meta_length: meta_length as u32,
+            meta_orig_length: meta_orig_length as u32,
+            priv_offset: priv_offset as u32,
+            priv_length: priv_length as u32,
+        };
+
+        let directory = WoffDirectory {
+            header,
+            table_directory: table_directory.into(),
+        };
+
+        encoder.writer().write(&encode_to_vec(directory)?)?;
+        encoder.writer().write(&data)
+    }
+}
+
+fn read_table<T>(entry: &WoffTableDirEntry, stream: &mut T) -> Result<Vec<u8>, Error>
+where
+    T: Read,
+{
+    let bytes = read_bytes(entry.comp_length as usize, stream)?;
+
+    let table = match entry.is_compressed() {
+        true => inflate(&bytes, entry.orig_length)?,
+        false => bytes,
+    };
+
+    match table.len() == entry.orig_length as usize {
+        true => Ok(table),
+        false => Err(Error::InvalidWoffTable(entry.tag)),
+    }
+}
+
+fn inflate(bytes: &[u8], orig_length: u32) -> Result<Vec<u8>, Error> {
+    let mut inflated = Vec::new();
+    let limit = orig_length as u64 + 1;
+    ZlibDecoder::new(bytes)
+        .take(limit)
+        .read_to_end(&mut inflated)?;
+    Ok(inflated)
+}
+
+fn deflate(bytes: &[u8], compression: Compression) -> Result<Vec<u8>, EncodeError> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), compression);
+    let io_error = |inner| EncodeError::Io { inner, index: 0 };
+    encoder.write_all(bytes).map_err(io_error)?;
+    encoder.finish().map_err(io_error)
+}
+
+fn sfnt_size(table_directory: &Seq<TableDirEntry>) -> usize {
+    let tables = table_directory
+        .iter()
+        .map(|e| padded_len(e.length as usize));
+    OFFSET_SUBTABLE_SIZE
+        + table_directory.as_slice().len() * TABLE_DIR_ENTRY_SIZE
+        + tables.sum::<usize>()
+}
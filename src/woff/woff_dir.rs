@@ -0,0 +1,95 @@
+use crate::{
+    error::Error,
+    table::tags::Tag,
+    utils::{
+        bytes::{read_be, write_be, FixedSize},
+        reader::{ReadSeq, TryFromStream},
+        types::Seq,
+    },
+};
+use bincode::{Decode, Encode};
+use std::io::{Read, Seek};
+
+#[derive(Debug, Encode)]
+pub struct WoffDirectory {
+    pub header: WoffHeader,
+    pub table_directory: Seq<WoffTableDirEntry>,
+}
+
+impl TryFromStream for WoffDirectory {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let header = WoffHeader::try_from_stream(stream)?;
+        let num_tables = header.num_tables as usize;
+        let table_directory = stream.read_seq(num_tables)?;
+
+        Ok(Self {
+            header,
+            table_directory,
+        })
+    }
+}
+
+impl WoffDirectory {
+    pub fn get_sorted_table_entries(&self) -> Vec<&WoffTableDirEntry> {
+        let mut entries = self.table_directory.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|e| e.offset);
+        entries
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct WoffHeader {
+    pub signature: u32,
+    pub flavor: u32,
+    pub length: u32,
+    pub num_tables: u16,
+    pub _reserved: u16,
+    pub total_sfnt_size: u32,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub meta_offset: u32,
+    pub meta_length: u32,
+    pub meta_orig_length: u32,
+    pub priv_offset: u32,
+    pub priv_length: u32,
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct WoffTableDirEntry {
+    pub tag: Tag,
+    pub offset: u32,
+    pub comp_length: u32,
+    pub orig_length: u32,
+    pub orig_checksum: u32,
+}
+
+impl FixedSize for WoffTableDirEntry {
+    const FIXED_SIZE: usize = 20;
+
+    fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+        Some(Self {
+            tag: read_be(bytes, 0)?,
+            offset: read_be(bytes, 4)?,
+            comp_length: read_be(bytes, 8)?,
+            orig_length: read_be(bytes, 12)?,
+            orig_checksum: read_be(bytes, 16)?,
+        })
+    }
+
+    fn to_be_slice(&self, bytes: &mut [u8]) {
+        write_be(bytes, 0, &self.tag);
+        write_be(bytes, 4, &self.offset);
+        write_be(bytes, 8, &self.comp_length);
+        write_be(bytes, 12, &self.orig_length);
+        write_be(bytes, 16, &self.orig_checksum);
+    }
+}
+
+impl WoffTableDirEntry {
+    pub fn is_compressed(&self) -> bool {
+        self.comp_length < self.orig_length
+    }
+}
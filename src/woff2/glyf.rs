@@ -0,0 +1,456 @@
+use crate::{
+    error::Error,
+    table::{
+        glyph::{ComponentGlyph, CompoundGlyph, Glyph, GlyphData, GlyphHeader, Point, SimpleGlyph},
+        Glyf, LocaFormat,
+    },
+    utils::{
+        bincode::{decode_from_reader, encode_to_vec},
+        reader::{read_bytes, TryFromStream},
+        types::Opt,
+    },
+    woff2::varint::{read_255_u16, write_255_u16},
+};
+use bincode::{Decode, Encode};
+use std::io::{Cursor, Read};
+
+const GLYF_HEADER_SIZE: usize = 36;
+const OVERLAP_SIMPLE_BITMAP: u16 = 1;
+const OFF_CURVE: u8 = 1 << 7;
+const COMPOUND_CONTOURS: i16 = -1;
+
+#[derive(Debug, Encode, Decode)]
+struct TransformedGlyfHeader {
+    reserved: u16,
+    option_flags: u16,
+    num_glyphs: u16,
+    index_format: u16,
+    n_contour_stream_size: u32,
+    n_points_stream_size: u32,
+    flag_stream_size: u32,
+    glyph_stream_size: u32,
+    composite_stream_size: u32,
+    bbox_stream_size: u32,
+    instruction_stream_size: u32,
+}
+
+#[derive(Default)]
+struct GlyfStreams {
+    n_contour: Vec<u8>,
+    n_points: Vec<u8>,
+    flags: Vec<u8>,
+    glyphs: Vec<u8>,
+    composites: Vec<u8>,
+    bbox_bitmap: Vec<u8>,
+    bboxes: Vec<u8>,
+    instructions: Vec<u8>,
+    overlap_bitmap: Vec<u8>,
+}
+
+pub fn decode_glyf(data: &[u8]) -> Result<(Glyf, LocaFormat), Error> {
+    let mut stream = Cursor::new(data);
+    let header = TransformedGlyfHeader::try_from_stream(&mut stream)?;
+    let num_glyphs = header.num_glyphs as usize;
+    let bitmap_size = bbox_bitmap_size(num_glyphs);
+    let bbox_stream_size = header.bbox_stream_size as usize;
+
+    if bbox_stream_size < bitmap_size {
+        return Err(Error::InvalidWoff2Data("glyf bbox stream is too short"));
+    }
+
+    let mut remaining = &data[GLYF_HEADER_SIZE.min(data.len())..];
+    let mut n_contour = Cursor::new(split(&mut remaining, header.n_contour_stream_size)?);
+    let mut n_points = Cursor::new(split(&mut remaining, header.n_points_stream_size)?);
+    let mut flags = Cursor::new(split(&mut remaining, header.flag_stream_size)?);
+    let mut glyphs = Cursor::new(split(&mut remaining, header.glyph_stream_size)?);
+    let mut composites = Cursor::new(split(&mut remaining, header.composite_stream_size)?);
+    let bbox_stream = split(&mut remaining, header.bbox_stream_size)?;
+    let mut instructions = Cursor::new(split(&mut remaining, header.instruction_stream_size)?);
+    let (bbox_bitmap, bboxes) = bbox_stream.split_at(bitmap_size);
+    let mut bboxes = Cursor::new(bboxes);
+
+    let overlap_bitmap = match header.option_flags & OVERLAP_SIMPLE_BITMAP {
+        0 => None,
+        _ => Some(split(&mut remaining, num_glyphs.div_ceil(8) as u32)?),
+    };
+
+    let mut glyf = Vec::new();
+
+    for glyph_id in 0..num_glyphs {
+        let contours: i16 = decode_from_reader(&mut n_contour)?;
+        let has_bbox = bit(bbox_bitmap, glyph_id);
+
+        let mut glyph = match contours {
+            0 => {
+                glyf.push(Opt::None);
+                continue;
+            }
+            COMPOUND_CONTOURS => {
+                if !has_bbox {
+                    return Err(Error::InvalidWoff2Data("compound glyph has no bbox"));
+                }
+
+                read_compound_glyph(&mut composites, &mut glyphs, &mut instructions)?
+            }
+            1.. => {
+                let overlap = overlap_bitmap.is_some_and(|b| bit(b, glyph_id));
+                let mut simple = read_simple_glyph(
+                    contours as usize,
+                    &mut n_points,
+                    &mut flags,
+                    &mut glyphs,
+                    &mut instructions,
+                )?;
+
+                if overlap {
+                    simple.set_overlap_simple(true);
+                }
+
+                Glyph::from_simple(simple)
+            }
+            _ => return Err(Error::InvalidWoff2Data("invalid glyph contour count")),
+        };
+
+        if has_bbox {
+            let bbox: [i16; 4] = decode_from_reader(&mut bboxes)?;
+            let [x_min, y_min, x_max, y_max] = bbox;
+            glyph.header = GlyphHeader {
+                number_of_contours: glyph.header.number_of_contours,
+                x_min,
+                y_min,
+                x_max,
+                y_max,
+            };
+        }
+
+        glyf.push(Opt::Some(glyph));
+    }
+
+    let format = match header.index_format {
+        0 => LocaFormat::Short,
+        _ => LocaFormat::Long,
+    };
+
+    Ok((Glyf::new(glyf.into()), format))
+}
+
+pub fn encode_glyf(glyf: &Glyf, format: LocaFormat) -> Result<Vec<u8>, Error> {
+    let num_glyphs = glyf.num_glyphs();
+    let mut streams = GlyfStreams {
+        bbox_bitmap: vec![0; bbox_bitmap_size(num_glyphs)],
+        overlap_bitmap: vec![0; num_glyphs.div_ceil(8)],
+        ..Default::default()
+    };
+
+    for (glyph_id, glyph) in glyf.glyphs.iter().enumerate() {
+        let Some(glyph) = glyph.as_option() else {
+            streams.n_contour.extend(0i16.to_be_bytes());
+            continue;
+        };
+
+        let bbox = match &glyph.data {
+            GlyphData::Simple(simple) if simple.end_pts_of_contours.as_slice().is_empty() => {
+                streams.n_contour.extend(0i16.to_be_bytes());
+                continue;
+            }
+            GlyphData::Simple(simple) => {
+                if simple.overlap_simple() {
+                    set_bit(&mut streams.overlap_bitmap, glyph_id);
+                }
+
+                write_simple_glyph(simple, &mut streams);
+                let computed = simple.bounds();
+                let header = &glyph.header;
+                let explicit = [header.x_min, header.y_min, header.x_max, header.y_max];
+                Some(explicit).filter(|&bbox| bbox != computed)
+            }
+            GlyphData::Compound(compound) => {
+                write_compound_glyph(compound, &mut streams)?;
+                let header = &glyph.header;
+                Some([header.x_min, header.y_min, header.x_max, header.y_max])
+            }
+        };
+
+        if let Some(bbox) = bbox {
+            set_bit(&mut streams.bbox_bitmap, glyph_id);
+            bbox.iter()
+                .for_each(|v| streams.bboxes.extend(v.to_be_bytes()));
+        }
+    }
+
+    let has_overlap = streams.overlap_bitmap.iter().any(|&b| b != 0);
+    let header = TransformedGlyfHeader {
+        reserved: 0,
+        option_flags: if has_overlap {
+            OVERLAP_SIMPLE_BITMAP
+        } else {
+            0
+        },
+        num_glyphs: num_glyphs as u16,
+        index_format: (format == LocaFormat::Long) as u16,
+        n_contour_stream_size: streams.n_contour.len() as u32,
+        n_points_stream_size: streams.n_points.len() as u32,
+        flag_stream_size: streams.flags.len() as u32,
+        glyph_stream_size: streams.glyphs.len() as u32,
+        composite_stream_size: streams.composites.len() as u32,
+        bbox_stream_size: (streams.bbox_bitmap.len() + streams.bboxes.len()) as u32,
+        instruction_stream_size: streams.instructions.len() as u32,
+    };
+
+    let mut bytes = encode_to_vec(header)?;
+    bytes.extend(streams.n_contour);
+    bytes.extend(streams.n_points);
+    bytes.extend(streams.flags);
+    bytes.extend(streams.glyphs);
+    bytes.extend(streams.composites);
+    bytes.extend(streams.bbox_bitmap);
+    bytes.extend(streams.bboxes);
+    bytes.extend(streams.instructions);
+
+    if has_overlap {
+        bytes.extend(streams.overlap_bitmap);
+    }
+
+    Ok(bytes)
+}
+
+fn read_simple_glyph<T>(
+    contours: usize,
+    n_points: &mut T,
+    flags: &mut T,
+    glyphs: &mut T,
+    instructions: &mut T,
+) -> Result<SimpleGlyph, Error>
+where
+    T: Read,
+{
+    let mut points = Vec::new();
+    let mut x = 0i16;
+    let mut y = 0i16;
+
+    for _ in 0..contours {
+        let count = read_255_u16(n_points)?;
+        let mut contour = Vec::with_capacity(count as usize);
+
+        for _ in 0..count {
+            let flag: u8 = decode_from_reader(flags)?;
+            let (dx, dy) = read_triplet(flag & !OFF_CURVE, glyphs)?;
+            x = x.wrapping_add(dx as i16);
+            y = y.wrapping_add(dy as i16);
+
+            contour.push(Point {
+                x,
+                y,
+                on_curve: flag & OFF_CURVE == 0,
+            });
+        }
+
+        points.push(contour);
+    }
+
+    let instruction_length = read_255_u16(glyphs)?;
+    let instructions = read_bytes(instruction_length as usize, instructions)?;
+
+    Ok(SimpleGlyph::from_contours(&points, instructions))
+}
+
+fn write_simple_glyph(glyph: &SimpleGlyph, streams: &mut GlyfStreams) {
+    let contours = glyph.contours();
+    let mut last = (0i32, 0i32);
+
+    streams
+        .n_contour
+        .extend((contours.len() as i16).to_be_bytes());
+
+    for contour in &contours {
+        write_255_u16(contour.len() as u16, &mut streams.n_points);
+
+        for point in contour {
+            let dx = point.x as i32 - last.0;
+            let dy = point.y as i32 - last.1;
+            let flag = write_triplet(dx, dy, &mut streams.glyphs);
+            let off_curve = if point.on_curve { 0 } else { OFF_CURVE };
+            streams.flags.push(flag | off_curve);
+            last = (point.x as i32, point.y as i32);
+        }
+    }
+
+    write_255_u16(glyph.instruction_length, &mut streams.glyphs);
+    streams.instructions.extend(glyph.instructions.iter());
+}
+
+fn read_compound_glyph(
+    composites: &mut Cursor<&[u8]>,
+    glyphs: &mut Cursor<&[u8]>,
+    instructions: &mut Cursor<&[u8]>,
+) -> Result<Glyph, Error> {
+    let mut components = Vec::new();
+    let mut has_instructions = false;
+
+    loop {
+        let component = ComponentGlyph::try_from_stream(composites)?;
+        let more_components = component.more_components();
+        has_instructions |= component.we_have_instructions();
+        components.push(component);
+
+        if !more_components {
+            break;
+        }
+    }
+
+    let (instruction_length, instructions) = match has_instructions {
+        true => {
+            let length = read_255_u16(glyphs)?;
+            let bytes = read_bytes(length as usize, instructions)?;
+            (Some(length), Some(bytes.into()))
+        }
+        false => (None, None),
+    };
+
+    Ok(Glyph {
+        header: GlyphHeader {
+            number_of_contours: COMPOUND_CONTOURS,
+            x_min: 0,
+            y_min: 0,
+            x_max: 0,
+            y_max: 0,
+        },
+        data: GlyphData::Compound(CompoundGlyph {
+            components: components.into(),
+            instruction_length: instruction_length.into(),
+            instructions: instructions.into(),
+        }),
+    })
+}
+
+fn write_compound_glyph(glyph: &CompoundGlyph, streams: &mut GlyfStreams) -> Result<(), Error> {
+    streams.n_contour.extend(COMPOUND_CONTOURS.to_be_bytes());
+
+    for component in glyph.components.iter() {
+        streams.composites.extend(encode_to_vec(component)?);
+    }
+
+    if let Some(instructions) = glyph.instructions.as_option() {
+        write_255_u16(instructions.as_slice().len() as u16, &mut streams.glyphs);
+        streams.instructions.extend(instructions.iter());
+    }
+
+    Ok(())
+}
+
+fn read_triplet<T>(flag: u8, stream: &mut T) -> Result<(i32, i32), Error>
+where
+    T: Read,
+{
+    let with_sign = |flag: u8, value: i32| if flag & 1 != 0 { value } else { -value };
+    let flag_value = flag as i32;
+
+    let (dx, dy) = match flag {
+        0..10 => {
+            let b0: u8 = decode_from_reader(stream)?;
+            (0, with_sign(flag, ((flag_value & 14) << 7) + b0 as i32))
+        }
+        10..20 => {
+            let b0: u8 = decode_from_reader(stream)?;
+            let dx = with_sign(flag, (((flag_value - 10) & 14) << 7) + b0 as i32);
+            (dx, 0)
+        }
+        20..84 => {
+            let b0 = flag_value - 20;
+            let b1: u8 = decode_from_reader(stream)?;
+            let b1 = b1 as i32;
+            (
+                with_sign(flag, 1 + (b0 & 0x30) + (b1 >> 4)),
+                with_sign(flag >> 1, 1 + ((b0 & 0x0C) << 2) + (b1 & 0x0F)),
+            )
+        }
+        84..120 => {
+            let b0 = flag_value - 84;
+            let [b1, b2]: [u8; 2] = decode_from_reader(stream)?;
+            (
+                with_sign(flag, 1 + ((b0 / 12) << 8) + b1 as i32),
+                with_sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + b2 as i32),
+            )
+        }
+        120..124 => {
+            let [b1, b2, b3]: [u8; 3] = decode_from_reader(stream)?;
+            let (b1, b2, b3) = (b1 as i32, b2 as i32, b3 as i32);
+            (
+                with_sign(flag, (b1 << 4) + (b2 >> 4)),
+                with_sign(flag >> 1, ((b2 & 0x0F) << 8) + b3),
+            )
+        }
+        _ => {
+            let [b1, b2, b3, b4]: [u8; 4] = decode_from_reader(stream)?;
+            (
+                with_sign(flag, (b1 as i32) << 8 | b2 as i32),
+                with_sign(flag >> 1, (b3 as i32) << 8 | b4 as i32),
+            )
+        }
+    };
+
+    Ok((dx, dy))
+}
+
+fn write_triplet(dx: i32, dy: i32, bytes: &mut Vec<u8>) -> u8 {
+    let abs_x = dx.unsigned_abs();
+    let abs_y = dy.unsigned_abs();
+    let x_sign = (dx >= 0) as u8;
+    let y_sign = (dy >= 0) as u8;
+    let xy_signs = x_sign + 2 * y_sign;
+
+    if dx == 0 && abs_y < 1280 {
+        bytes.push(abs_y as u8);
+        ((abs_y & 0xF00) >> 7) as u8 + y_sign
+    } else if dy == 0 && abs_x < 1280 {
+        bytes.push(abs_x as u8);
+        10 + ((abs_x & 0xF00) >> 7) as u8 + x_sign
+    } else if abs_x < 65 && abs_y < 65 {
+        bytes.push((((abs_x - 1) & 0x0F) << 4 | (abs_y - 1) & 0x0F) as u8);
+        20 + ((abs_x - 1) & 0x30) as u8 + (((abs_y - 1) & 0x30) >> 2) as u8 + xy_signs
+    } else if abs_x < 769 && abs_y < 769 {
+        bytes.extend([(abs_x - 1) as u8, (abs_y - 1) as u8]);
+        84 + 12 * (((abs_x - 1) & 0x300) >> 8) as u8 + (((abs_y - 1) & 0x300) >> 6) as u8 + xy_signs
+    } else if abs_x < 4096 && abs_y < 4096 {
+        bytes.extend([
+            (abs_x >> 4) as u8,
+            ((abs_x & 0x0F) << 4 | abs_y >> 8) as u8,
+            abs_y as u8,
+        ]);
+        120 + xy_signs
+    } else {
+        bytes.extend([
+            (abs_x >> 8) as u8,
+            abs_x as u8,
+            (abs_y >> 8) as u8,
+            abs_y as u8,
+        ]);
+        124 + xy_signs
+    }
+}
+
+fn split<'a>(data: &mut &'a [u8], length: u32) -> Result<&'a [u8], Error> {
+    let length = length as usize;
+
+    if length > data.len() {
+        return Err(Error::InvalidWoff2Data("glyf stream exceeds the table"));
+    }
+
+    let (head, tail) = data.split_at(length);
+    *data = tail;
+    Ok(head)
+}
+
+fn bbox_bitmap_size(num_glyphs: usize) -> usize {
+    num_glyphs.div_ceil(32) * 4
+}
+
+fn bit(bitmap: &[u8], index: usize) -> bool {
+    bitmap
+        .get(index >> 3)
+        .is_some_and(|b| b & (0x80 >> (index & 7)) != 0)
+}
+
+fn set_bit(bitmap: &mut [u8], index: usize) {
+    bitmap[index >> 3] |= 0x80 >> (index & 7);
+}
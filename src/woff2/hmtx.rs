@@ -0,0 +1,97 @@
+use crate::{
+    error::Error,
+    table::{Glyf, Hmtx, LongHorMetric},
+    utils::{bincode::decode_from_reader, reader::ReadSeq},
+};
+use std::io::Cursor;
+
+const PROPORTIONAL_LSB_ABSENT: u8 = 1;
+const MONOSPACED_LSB_ABSENT: u8 = 1 << 1;
+const RESERVED_FLAGS: u8 = !(PROPORTIONAL_LSB_ABSENT | MONOSPACED_LSB_ABSENT);
+
+pub fn decode_hmtx(data: &[u8], num_h_metrics: usize, glyf: &Glyf) -> Result<Hmtx, Error> {
+    let mut stream = Cursor::new(data);
+    let flags: u8 = decode_from_reader(&mut stream)?;
+    let num_glyphs = glyf.num_glyphs();
+
+    if flags & RESERVED_FLAGS != 0 || num_h_metrics > num_glyphs {
+        return Err(Error::InvalidWoff2Data("invalid transformed hmtx"));
+    }
+
+    let advance_widths = stream.read_seq::<u16>(num_h_metrics)?;
+
+    let h_metrics_lsb = match flags & PROPORTIONAL_LSB_ABSENT {
+        0 => stream.read_seq::<i16>(num_h_metrics)?.into_vec(),
+        _ => (0..num_h_metrics).map(|g| x_min(glyf, g)).collect(),
+    };
+
+    let left_side_bearing = match flags & MONOSPACED_LSB_ABSENT {
+        0 => stream.read_seq(num_glyphs - num_h_metrics)?,
+        _ => (num_h_metrics..num_glyphs)
+            .map(|g| x_min(glyf, g))
+            .collect(),
+    };
+
+    let h_metrics = advance_widths
+        .into_iter()
+        .zip(h_metrics_lsb)
+        .map(|(advance_width, left_side_bearing)| LongHorMetric {
+            advance_width,
+            left_side_bearing,
+        })
+        .collect();
+
+    Ok(Hmtx {
+        h_metrics,
+        left_side_bearing,
+    })
+}
+
+pub fn encode_hmtx(hmtx: &Hmtx, glyf: &Glyf) -> Option<Vec<u8>> {
+    let h_metrics = hmtx.h_metrics.as_slice();
+    let num_h_metrics = h_metrics.len();
+    let left_side_bearing = hmtx.left_side_bearing.as_slice();
+
+    let proportional_absent = h_metrics
+        .iter()
+        .enumerate()
+        .all(|(g, m)| m.left_side_bearing == x_min(glyf, g));
+    let monospaced_absent = left_side_bearing
+        .iter()
+        .enumerate()
+        .all(|(g, &lsb)| lsb == x_min(glyf, g + num_h_metrics));
+
+    if !proportional_absent && !monospaced_absent {
+        return None;
+    }
+
+    let mut flags = 0;
+    let mut bytes = Vec::new();
+
+    h_metrics
+        .iter()
+        .for_each(|m| bytes.extend(m.advance_width.to_be_bytes()));
+
+    match proportional_absent {
+        true => flags |= PROPORTIONAL_LSB_ABSENT,
+        false => h_metrics
+            .iter()
+            .for_each(|m| bytes.extend(m.left_side_bearing.to_be_bytes())),
+    }
+
+    match monospaced_absent {
+        true => flags |= MONOSPACED_LSB_ABSENT,
+        false => left_side_bearing
+            .iter()
+            .for_each(|lsb| bytes.extend(lsb.to_be_bytes())),
+    }
+
+    bytes.insert(0, flags);
+    Some(bytes)
+}
+
+fn x_min(glyf: &Glyf, glyph_id: usize) -> i16 {
+    glyf.get(glyph_id as u16)
+        .filter(|g| g.header.number_of_contours != 0)
+        .map_or(0, |g| g.header.x_min)
+}
@@ -0,0 +1,398 @@
+mod glyf;
+mod hmtx;
+mod varint;
+pub mod woff2_dir;
+
+pub use glyf::{decode_glyf, encode_glyf};
+pub use hmtx::{decode_hmtx, encode_hmtx};
+pub use varint::{read_255_u16, read_base128, write_255_u16, write_base128};
+
+use crate::{
+    error::Error,
+    table::{
+        tags::{self, Tag},
+        GetFontTable, Hhea, Loca, LocaFormat, Maxp,
+    },
+    ttf::{
+        font::{write_sfnt, Font, MAX_SFNT_SIZE},
+        font_dir::padded_len,
+    },
+    utils::{
+        bincode::{decode_from_slice, encode_to_vec},
+        reader::{read_bytes, TryFromStream},
+    },
+    woff2::woff2_dir::{Woff2Directory, Woff2Header, Woff2TableDirEntry},
+};
+use bincode::{
+    enc::{write::Writer, Encoder},
+    error::EncodeError,
+    Encode,
+};
+use brotli::{CompressorWriter, Decompressor};
+use std::{
+    collections::BTreeMap,
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+};
+
+pub const WOFF2_SIGNATURE: u32 = 0x774F4632;
+
+const COLLECTION_FLAVOR: u32 = 0x74746366;
+const WOFF2_HEADER_SIZE: usize = 48;
+const INDEX_TO_LOC_FORMAT_OFFSET: usize = 50;
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BROTLI_WINDOW_SIZE: u32 = 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Woff2Options {
+    pub quality: u32,
+    pub transform_glyf: bool,
+    pub transform_hmtx: bool,
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Default for Woff2Options {
+    fn default() -> Self {
+        Self {
+            quality: 11,
+            transform_glyf: true,
+            transform_hmtx: true,
+            major_version: 0,
+            minor_version: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Woff2 {
+    pub font: Font,
+    pub metadata: Option<Vec<u8>>,
+    pub private_data: Option<Vec<u8>>,
+    pub options: Woff2Options,
+}
+
+impl Woff2 {
+    pub fn new(font: Font) -> Self {
+        Self {
+            font,
+            metadata: None,
+            private_data: None,
+            options: Woff2Options::default(),
+        }
+    }
+}
+
+impl TryFromStream for Woff2 {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let start = stream.stream_position()?;
+        let directory = Woff2Directory::try_from_stream(stream)?;
+        let header = &directory.header;
+
+        if header.flavor == COLLECTION_FLAVOR {
+            return Err(Error::InvalidWoff2Data(
+                "font collections are not supported",
+            ));
+        }
+
+        let compressed = read_bytes(header.total_compressed_size as usize, stream)?;
+        let data_length = directory
+            .table_directory
+            .iter()
+            .map(|e| e.data_length() as u64)
+            .sum::<u64>();
+
+        if data_length > MAX_SFNT_SIZE {
+            return Err(Error::DataTooLarge(data_length));
+        }
+
+        if header.meta_orig_length as u64 > MAX_SFNT_SIZE {
+            return Err(Error::DataTooLarge(header.meta_orig_length.into()));
+        }
+
+        let data = decompress(&compressed, data_length)?;
+
+        let mut offset = 0;
+        let mut raw_tables = Vec::new();
+
+        for entry in &directory.table_directory {
+            let length = entry.data_length() as usize;
+            raw_tables.push((entry, &data[offset..offset + length]));
+            offset += length;
+        }
+
+        let tables = reconstruct_tables(&raw_tables)?;
+        let (_, sfnt) = write_sfnt(header.flavor, tables)?;
+
+        let metadata = match header.meta_length {
+            0 => None,
+            length => {
+                stream.seek(SeekFrom::Start(start + header.meta_offset as u64))?;
+                let compressed = read_bytes(length as usize, stream)?;
+                let metadata = decompress(&compressed, header.meta_orig_length as u64)?;
+                Some(metadata)
+            }
+        };
+
+        let private_data = match header.priv_length {
+            0 => None,
+            length => {
+                stream.seek(SeekFrom::Start(start + header.priv_offset as u64))?;
+                Some(read_bytes(length as usize, stream)?)
+            }
+        };
+
+        Ok(Self {
+            font: Font::from_sfnt_stream(&mut Cursor::new(sfnt))?,
+            metadata,
+            private_data,
+            options: Woff2Options {
+                major_version: header.major_version,
+                minor_version: header.minor_version,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+impl Encode for Woff2 {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let (font_directory, sfnt) = self.font.encode_sfnt()?;
+        let transformed = self.transformed_tables()?;
+        let mut entries = font_directory.table_directory.iter().collect::<Vec<_>>();
+
+        entries.sort_by_key(|e| e.tag);
+
+        if let Some(loca) = entries.iter().position(|e| e.tag == tags::LOCA) {
+            let loca = entries.remove(loca);
+
+            match entries.iter().position(|e| e.tag == tags::GLYF) {
+                Some(glyf) => entries.insert(glyf + 1, loca),
+                None => entries.push(loca),
+            }
+        }
+
+        let mut table_directory = Vec::new();
+        let mut data = Vec::new();
+
+        for entry in entries {
+            let start = entry.offset as usize;
+            let table = &sfnt[start..start + entry.length as usize];
+
+            let transform_length = match transformed.get(&entry.tag) {
+                Some(bytes) => {
+                    data.extend(bytes);
+                    Some(bytes.len() as u32)
+                }
+                None => {
+                    data.extend(table);
+                    None
+                }
+            };
+
+            table_directory.push(Woff2TableDirEntry::new(
+                entry.tag,
+                entry.length,
+                transform_length,
+            ));
+        }
+
+        let compressed = compress(&data, self.options.quality)?;
+        let entries_size = table_directory
+            .iter()
+            .map(encode_to_vec)
+            .try_fold(0, |size, bytes| bytes.map(|b| size + b.len()))?;
+        let header_size = WOFF2_HEADER_SIZE + entries_size;
+        let mut data = compressed.clone();
+
+        let (meta_offset, meta_length, meta_orig_length) = match &self.metadata {
+            Some(metadata) => {
+                let compressed = compress(metadata, self.options.quality)?;
+                data.resize(padded_len(header_size + data.len()) - header_size, 0);
+                let offset = header_size + data.len();
+                data.extend(&compressed);
+                (offset, compressed.len(), metadata.len())
+            }
+            None => (0, 0, 0),
+        };
+
+        let (priv_offset, priv_length) = match &self.private_data {
+            Some(private_data) => {
+                data.resize(padded_len(header_size + data.len()) - header_size, 0);
+                let offset = header_size + data.len();
+                data.extend(private_data);
+                (offset, private_data.len())
+            }
+            None => (0, 0),
+        };
+
+        data.resize(padded_len(header_size + data.len()) - header_size, 0);
+
+        let header = Woff2Header {
+            signature: WOFF2_SIGNATURE,
+            flavor: font_directory.offset_subtable.scaler_type,
+            length: (header_size + data.len()) as u32,
+            num_tables: table_directory.len() as u16,
+            _reserved: 0,
+            total_sfnt_size: sfnt.len() as u32,
+            total_compressed_size: compressed.len() as u32,
+            major_version: self.options.major_version,
+            minor_version: self.options.minor_version,
+            meta_offset: meta_offset as u32,
+            meta_length: meta_length as u32,
+            meta_orig_length: meta_orig_length as u32,
+            priv_offset: priv_offset as u32,
+            priv_length: priv_length as u32,
+        };
+
+        let directory = Woff2Directory {
+            header,
+            table_directory,
+        };
+
+        directory.encode(encoder)?;
+        encoder.writer().write(&data)
+    }
+}
+
+impl Woff2 {
+    fn transformed_tables(&self) -> Result<BTreeMap<Tag, Vec<u8>>, EncodeError> {
+        let mut tables = BTreeMap::new();
+        let font = &self.font;
+
+        if !self.options.transform_glyf
+            || !font.contains_table(tags::GLYF)
+            || !font.contains_table(tags::LOCA)
+        {
+            return Ok(tables);
+        }
+
+        let (Ok(glyf), Ok(loca)) = (font.glyf(), font.loca()) else {
+            return Ok(tables);
+        };
+
+        let transformed = encode_glyf(glyf, loca.format)
+            .map_err(|_| EncodeError::Other("failed to transform the glyf table"))?;
+        tables.insert(tags::GLYF, transformed);
+        tables.insert(tags::LOCA, Vec::new());
+
+        if self.options.transform_hmtx && font.contains_table(tags::HMTX) {
+            if let Some(hmtx) = font.hmtx().ok().and_then(|h| encode_hmtx(h, glyf)) {
+                tables.insert(tags::HMTX, hmtx);
+            }
+        }
+
+        Ok(tables)
+    }
+}
+
+fn reconstruct_tables(
+    raw_tables: &[(&Woff2TableDirEntry, &[u8])],
+) -> Result<BTreeMap<Tag, Vec<u8>>, Error> {
+    let mut tables = BTreeMap::new();
+    let mut glyf = None;
+
+    for &(entry, data) in raw_tables {
+        match (entry.tag, entry.is_transformed()) {
+            (tags::GLYF, true) => {
+                let (decoded, mut format) = decode_glyf(data)?;
+                let offsets = decoded.offsets()?;
+
+                if !format.fits(offsets.as_slice()) {
+                    format = LocaFormat::Long;
+                }
+
+                let loca = Loca { offsets, format };
+                tables.insert(tags::GLYF, encode_to_vec(&decoded)?);
+                tables.insert(tags::LOCA, encode_to_vec(&loca)?);
+                glyf = Some((decoded, format));
+            }
+            (tags::LOCA, true) => {}
+            (tags::HMTX, true) => {}
+            (tag, false) => {
+                tables.insert(tag, data.to_vec());
+            }
+            (_, true) => return Err(Error::InvalidWoff2Data("unsupported table transform")),
+        }
+    }
+
+    let has_glyf = raw_tables.iter().any(|(e, _)| e.tag == tags::GLYF);
+    let has_loca = raw_tables.iter().any(|(e, _)| e.tag == tags::LOCA);
+
+    if has_loca && !tables.contains_key(&tags::LOCA) || has_glyf != has_loca {
+        return Err(Error::InvalidWoff2Data(
+            "glyf and loca must be transformed together",
+        ));
+    }
+
+    if let Some((_, format)) = &glyf {
+        if let Some(head) = tables.get_mut(&tags::HEAD) {
+            let position = INDEX_TO_LOC_FORMAT_OFFSET;
+            let value = (*format == LocaFormat::Long) as i16;
+
+            if let Some(bytes) = head.get_mut(position..position + 2) {
+                bytes.copy_from_slice(&value.to_be_bytes());
+            }
+        }
+    }
+
+    if let Some((entry, data)) = raw_tables
+        .iter()
+        .find(|(e, _)| e.tag == tags::HMTX && e.is_transformed())
+    {
+        let Some((glyf, _)) = &glyf else {
+            return Err(Error::InvalidWoff2Data(
+                "hmtx transform requires a transformed glyf",
+            ));
+        };
+
+        let hhea: Hhea = decode_from_slice(table_bytes(&tables, tags::HHEA)?)?;
+        let maxp: Maxp = decode_from_slice(table_bytes(&tables, tags::MAXP)?)?;
+
+        if maxp.num_glyphs as usize != glyf.num_glyphs() {
+            return Err(Error::InvalidWoff2Data("maxp and glyf glyph counts differ"));
+        }
+
+        let hmtx = decode_hmtx(data, hhea.num_of_long_hor_metrics as usize, glyf)?;
+        let hmtx = encode_to_vec(hmtx)?;
+
+        if hmtx.len() != entry.orig_length as usize {
+            return Err(Error::InvalidWoff2Data(
+                "hmtx does not match its original length",
+            ));
+        }
+
+        tables.insert(tags::HMTX, hmtx);
+    }
+
+    Ok(tables)
+}
+
+fn table_bytes(tables: &BTreeMap<Tag, Vec<u8>>, tag: Tag) -> Result<&[u8], Error> {
+    match tables.get(&tag) {
+        Some(bytes) => Ok(bytes),
+        None => Err(Error::MissingRequiredTable),
+    }
+}
+
+fn decompress(bytes: &[u8], length: u64) -> Result<Vec<u8>, Error> {
+    let mut data = Vec::new();
+    let decompressor = Decompressor::new(bytes, BROTLI_BUFFER_SIZE);
+    decompressor.take(length + 1).read_to_end(&mut data)?;
+
+    match data.len() as u64 == length {
+        true => Ok(data),
+        false => Err(Error::InvalidWoff2Data("decompressed size does not match")),
+    }
+}
+
+fn compress(bytes: &[u8], quality: u32) -> Result<Vec<u8>, EncodeError> {
+    let mut writer =
+        CompressorWriter::new(Vec::new(), BROTLI_BUFFER_SIZE, quality, BROTLI_WINDOW_SIZE);
+    let io_error = |inner| EncodeError::Io { inner, index: 0 };
+    writer.write_all(bytes).map_err(io_error)?;
+    writer.flush().map_err(io_error)?;
+    Ok(writer.into_inner())
+}
@@ -0,0 +1,84 @@
+use crate::{error::Error, utils::bincode::decode_from_reader};
+use std::io::Read;
+
+const ONE_MORE_BYTE_CODE_1: u8 = 255;
+const ONE_MORE_BYTE_CODE_2: u8 = 254;
+const WORD_CODE: u8 = 253;
+const LOWEST_U_CODE: u16 = 253;
+const SECOND_U_CODE: u16 = LOWEST_U_CODE * 2;
+const THIRD_U_CODE: u16 = SECOND_U_CODE + 256;
+const MAX_BASE128_BYTES: usize = 5;
+
+pub fn read_base128<T>(stream: &mut T) -> Result<u32, Error>
+where
+    T: Read,
+{
+    let mut value = 0u32;
+
+    for i in 0..MAX_BASE128_BYTES {
+        let byte: u8 = decode_from_reader(stream)?;
+
+        if i == 0 && byte == 0x80 {
+            return Err(Error::InvalidWoff2Data("UIntBase128 has leading zeros"));
+        }
+
+        if value & 0xFE000000 != 0 {
+            return Err(Error::InvalidWoff2Data("UIntBase128 overflows 32 bits"));
+        }
+
+        value = value << 7 | (byte & 0x7F) as u32;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(Error::InvalidWoff2Data(
+        "UIntBase128 is longer than 5 bytes",
+    ))
+}
+
+pub fn write_base128(value: u32, bytes: &mut Vec<u8>) {
+    let length = (1..MAX_BASE128_BYTES)
+        .take_while(|&i| value >> (7 * i) != 0)
+        .count()
+        + 1;
+
+    for i in (0..length).rev() {
+        let byte = (value >> (7 * i)) as u8 & 0x7F;
+        let more = if i == 0 { 0 } else { 0x80 };
+        bytes.push(byte | more);
+    }
+}
+
+pub fn read_255_u16<T>(stream: &mut T) -> Result<u16, Error>
+where
+    T: Read,
+{
+    let code: u8 = decode_from_reader(stream)?;
+
+    let value = match code {
+        WORD_CODE => decode_from_reader(stream)?,
+        ONE_MORE_BYTE_CODE_1 => decode_from_reader::<u8, _>(stream)? as u16 + LOWEST_U_CODE,
+        ONE_MORE_BYTE_CODE_2 => decode_from_reader::<u8, _>(stream)? as u16 + SECOND_U_CODE,
+        _ => code as u16,
+    };
+
+    Ok(value)
+}
+
+pub fn write_255_u16(value: u16, bytes: &mut Vec<u8>) {
+    match value {
+        0..LOWEST_U_CODE => bytes.push(value as u8),
+        LOWEST_U_CODE..SECOND_U_CODE => {
+            bytes.extend([ONE_MORE_BYTE_CODE_1, (value - LOWEST_U_CODE) as u8])
+        }
+        SECOND_U_CODE..THIRD_U_CODE => {
+            bytes.extend([ONE_MORE_BYTE_CODE_2, (value - SECOND_U_CODE) as u8])
+        }
+        _ => {
+            bytes.push(WORD_CODE);
+            bytes.extend(value.to_be_bytes());
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    error::Error,
+    table::tags::{self, Tag},
+    utils::{bincode::decode_from_reader, reader::TryFromStream},
+    woff2::varint::{read_base128, write_base128},
+};
+use bincode::{
+    enc::{write::Writer, Encoder},
+    error::EncodeError,
+    Decode, Encode,
+};
+use std::io::{Read, Seek};
+
+const ARBITRARY_TAG: u8 = 63;
+const TAG_INDEX_MASK: u8 = 0x3F;
+const TRANSFORM_SHIFT: u8 = 6;
+const NULL_GLYF_TRANSFORM: u8 = 3;
+
+pub const KNOWN_TAGS: [Tag; 63] = [
+    u32::from_be_bytes(*b"cmap"),
+    u32::from_be_bytes(*b"head"),
+    u32::from_be_bytes(*b"hhea"),
+    u32::from_be_bytes(*b"hmtx"),
+    u32::from_be_bytes(*b"maxp"),
+    u32::from_be_bytes(*b"name"),
+    u32::from_be_bytes(*b"OS/2"),
+    u32::from_be_bytes(*b"post"),
+    u32::from_be_bytes(*b"cvt "),
+    u32::from_be_bytes(*b"fpgm"),
+    u32::from_be_bytes(*b"glyf"),
+    u32::from_be_bytes(*b"loca"),
+    u32::from_be_bytes(*b"prep"),
+    u32::from_be_bytes(*b"CFF "),
+    u32::from_be_bytes(*b"VORG"),
+    u32::from_be_bytes(*b"EBDT"),
+    u32::from_be_bytes(*b"EBLC"),
+    u32::from_be_bytes(*b"gasp"),
+    u32::from_be_bytes(*b"hdmx"),
+    u32::from_be_bytes(*b"kern"),
+    u32::from_be_bytes(*b"LTSH"),
+    u32::from_be_bytes(*b"PCLT"),
+    u32::from_be_bytes(*b"VDMX"),
+    u32::from_be_bytes(*b"vhea"),
+    u32::from_be_bytes(*b"vmtx"),
+    u32::from_be_bytes(*b"BASE"),
+    u32::from_be_bytes(*b"GDEF"),
+    u32::from_be_bytes(*b"GPOS"),
+    u32::from_be_bytes(*b"GSUB"),
+    u32::from_be_bytes(*b"EBSC"),
+    u32::from_be_bytes(*b"JSTF"),
+    u32::from_be_bytes(*b"MATH"),
+    u32::from_be_bytes(*b"CBDT"),
+    u32::from_be_bytes(*b"CBLC"),
+    u32::from_be_bytes(*b"COLR"),
+    u32::from_be_bytes(*b"CPAL"),
+    u32::from_be_bytes(*b"SVG "),
+    u32::from_be_bytes(*b"sbix"),
+    u32::from_be_bytes(*b"acnt"),
+    u32::from_be_bytes(*b"avar"),
+    u32::from_be_bytes(*b"bdat"),
+    u32::from_be_bytes(*b"bloc"),
+    u32::from_be_bytes(*b"bsln"),
+    u32::from_be_bytes(*b"cvar"),
+    u32::from_be_bytes(*b"fdsc"),
+    u32::from_be_bytes(*b"feat"),
+    u32::from_be_bytes(*b"fmtx"),
+    u32::from_be_bytes(*b"fvar"),
+    u32::from_be_bytes(*b"gvar"),
+    u32::from_be_bytes(*b"hsty"),
+    u32::from_be_bytes(*b"just"),
+    u32::from_be_bytes(*b"lcar"),
+    u32::from_be_bytes(*b"mort"),
+    u32::from_be_bytes(*b"morx"),
+    u32::from_be_bytes(*b"opbd"),
+    u32::from_be_bytes(*b"prop"),
+    u32::from_be_bytes(*b"trak"),
+    u32::from_be_bytes(*b"Zapf"),
+    u32::from_be_bytes(*b"Silf"),
+    u32::from_be_bytes(*b"Glat"),
+    u32::from_be_bytes(*b"Gloc"),
+    u32::from_be_bytes(*b"Feat"),
+    u32::from_be_bytes(*b"Sill"),
+];
+
+#[derive(Debug)]
+pub struct Woff2Directory {
+    pub header: Woff2Header,
+    pub table_directory: Vec<Woff2TableDirEntry>,
+}
+
+impl TryFromStream for Woff2Directory {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let header = Woff2Header::try_from_stream(stream)?;
+        let mut table_directory = Vec::new();
+
+        for _ in 0..header.num_tables {
+            table_directory.push(Woff2TableDirEntry::try_from_stream(stream)?);
+        }
+
+        Ok(Self {
+            header,
+            table_directory,
+        })
+    }
+}
+
+impl Encode for Woff2Directory {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.header.encode(encoder)?;
+
+        for entry in &self.table_directory {
+            entry.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+pub struct Woff2Header {
+    pub signature: u32,
+    pub flavor: u32,
+    pub length: u32,
+    pub num_tables: u16,
+    pub _reserved: u16,
+    pub total_sfnt_size: u32,
+    pub total_compressed_size: u32,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub meta_offset: u32,
+    pub meta_length: u32,
+    pub meta_orig_length: u32,
+    pub priv_offset: u32,
+    pub priv_length: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Woff2TableDirEntry {
+    pub tag: Tag,
+    pub transform_version: u8,
+    pub orig_length: u32,
+    pub transform_length: Option<u32>,
+}
+
+impl TryFromStream for Woff2TableDirEntry {
+    fn try_from_stream<T>(stream: &mut T) -> Result<Self, Error>
+    where
+        T: Read + Seek,
+    {
+        let flags: u8 = decode_from_reader(stream)?;
+        let transform_version = flags >> TRANSFORM_SHIFT;
+
+        let tag = match flags & TAG_INDEX_MASK {
+            ARBITRARY_TAG => decode_from_reader(stream)?,
+            index => KNOWN_TAGS[index as usize],
+        };
+
+        let orig_length = read_base128(stream)?;
+        let mut entry = Self {
+            tag,
+            transform_version,
+            orig_length,
+            transform_length: None,
+        };
+
+        if entry.is_transformed() {
+            entry.transform_length = Some(read_base128(stream)?);
+        }
+
+        Ok(entry)
+    }
+}
+
+impl Encode for Woff2TableDirEntry {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let index = KNOWN_TAGS.iter().position(|&t| t == self.tag);
+        let flags = index.unwrap_or(ARBITRARY_TAG as usize) as u8;
+        let mut bytes = vec![flags | self.transform_version << TRANSFORM_SHIFT];
+
+        if index.is_none() {
+            bytes.extend(self.tag.to_be_bytes());
+        }
+
+        write_base128(self.orig_length, &mut bytes);
+
+        if let Some(length) = self.transform_length {
+            write_base128(length, &mut bytes);
+        }
+
+        encoder.writer().write(&bytes)
+    }
+}
+
+impl Woff2TableDirEntry {
+    pub fn new(tag: Tag, orig_length: u32, transform_length: Option<u32>) -> Self {
+        let transformed = transform_length.is_some();
+
+        let transform_version = match tag {
+            tags::GLYF | tags::LOCA if !transformed => NULL_GLYF_TRANSFORM,
+            tags::GLYF | tags::LOCA => 0,
+            _ if transformed => 1,
+            _ => 0,
+        };
+
+        Self {
+            tag,
+            transform_version,
+            orig_length,
+            transform_length,
+        }
+    }
+
+    pub fn is_transformed(&self) -> bool {
+        match self.tag {
+            tags::GLYF | tags::LOCA => self.transform_version != NULL_GLYF_TRANSFORM,
+            _ => self.transform_version != 0,
+        }
+    }
+
+    pub fn data_length(&self) -> u32 {
+        self.transform_length.unwrap_or(self.orig_length)
+    }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::fixture;
+use fontori::{
+    table::{glyph::Glyph, tags, FontTable},
+    ttf::{checksum::ChecksumMismatch, font::Font, font_ref::FontRef},
+    utils::reader::TryFromStream,
+};
+use std::io::Cursor;
+
+fn sfnt(name: &str) -> Vec<u8> {
+    let font = Font::try_from_stream(&mut Cursor::new(fixture(name))).unwrap();
+    font.encode_sfnt().unwrap().1
+}
+
+fn verify(bytes: &[u8]) -> Vec<ChecksumMismatch> {
+    let font = Font::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+    font.verify_checksums().unwrap()
+}
+
+// the first byte of glyph padding, which the parser skips over
+fn glyph_padding(bytes: &[u8]) -> usize {
+    let font = FontRef::new(bytes).unwrap();
+    let loca = font.loca().unwrap();
+    let glyf = font.table_data(tags::GLYF).unwrap();
+    let glyf_offset = glyf.as_ptr() as usize - bytes.as_ptr() as usize;
+
+    (0..loca.len() as u16 - 1)
+        .filter_map(|glyph_id| loca.glyph_range(glyph_id))
+        .find_map(|range| {
+            let mut stream = Cursor::new(&glyf[range.clone()]);
+            Glyph::try_from_stream(&mut stream).unwrap();
+            let parsed = stream.position() as usize;
+            (parsed < range.len()).then_some(glyf_offset + range.start + parsed)
+        })
+        .unwrap()
+}
+
+#[test]
+fn accepts_untouched_files() {
+    for name in ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"] {
+        assert_eq!(verify(&sfnt(name)), []);
+    }
+}
+
+#[test]
+fn checks_the_bytes_that_were_read() {
+    let mut bytes = sfnt("OpenSans-Regular.woff2");
+    let position = glyph_padding(&bytes);
+    bytes[position] = 0xAA;
+
+    let mismatches = verify(&bytes);
+
+    assert!(mismatches.iter().any(|m| matches!(
+        m,
+        ChecksumMismatch::Table {
+            tag: tags::GLYF,
+            ..
+        }
+    )));
+    assert!(mismatches
+        .iter()
+        .any(|m| matches!(m, ChecksumMismatch::Adjustment { .. })));
+}
+
+#[test]
+fn ignores_edits_made_after_loading() {
+    let bytes = sfnt("SourceCodePro-Medium.woff2");
+    let mut font = Font::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+
+    if let Some(FontTable::Head(head)) = font.table_mut(tags::HEAD) {
+        head.font_revision += 1;
+    }
+
+    assert_eq!(font.verify_checksums().unwrap(), []);
+}
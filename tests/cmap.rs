@@ -0,0 +1,222 @@
+mod common;
+
+use common::{corpus, fixture};
+use fontori::{
+    table::{Cmap, CmapSubtable, EncodingSubtable, GetFontTable, VariantGlyph},
+    ttf::font::Font,
+    utils::{bincode::encode_to_vec, reader::TryFromStream, types::Opt},
+};
+use std::{collections::BTreeMap, io::Cursor};
+
+const FIXTURES: [&str; 2] = ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"];
+
+fn corpus_cmap() -> Cmap {
+    Cmap::try_from_stream(&mut Cursor::new(corpus::cmap())).unwrap()
+}
+
+#[test]
+fn looks_up_the_corpus_cmap() {
+    let cmap = corpus_cmap();
+
+    // the (3, 10) record points at the format 12 subtable
+    assert!(matches!(
+        cmap.best_subtable(),
+        Some(CmapSubtable::Format12(_))
+    ));
+    assert_eq!(cmap.glyph_index(' '), Some(3));
+    assert_eq!(cmap.glyph_index('A'), Some(36));
+    assert_eq!(cmap.glyph_index('\u{1F602}'), Some(3));
+    assert_eq!(cmap.glyph_index('\u{E00FF}'), Some(257));
+    assert_eq!(cmap.glyph_index('\u{7F}'), None);
+    assert_eq!(cmap.glyph_index('\u{1F650}'), None);
+}
+
+#[test]
+fn lists_corpus_mappings_in_both_directions() {
+    let cmap = corpus_cmap();
+    let mappings = cmap.mappings().collect::<Vec<_>>();
+    let reverse = cmap.reverse_mappings();
+
+    assert_eq!(mappings.len(), 95 + 80 + 256);
+    assert!(mappings.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(reverse[&3], [0x20, 0x1F602, 0xE0001]);
+    assert_eq!(reverse[&1], [0x1F600]);
+    assert_eq!(
+        reverse.values().map(Vec::len).sum::<usize>(),
+        mappings.len()
+    );
+}
+
+#[test]
+fn prefers_full_unicode_encodings() {
+    let mut cmap = corpus_cmap();
+    let mut records = vec![
+        EncodingSubtable::new(1, 0, 4),
+        EncodingSubtable::new(0, 3, 7),
+        EncodingSubtable::new(3, 1, 6),
+        EncodingSubtable::new(0, 4, 1),
+        EncodingSubtable::new(3, 10, 3),
+    ];
+
+    // format 13, 12, 8 and 6 subtables, so each step changes where 'C' maps
+    for expected in [Some(3), Some(38), Some(3), Some(2), None] {
+        cmap.encoding_subtables = records.iter().map(clone_record).collect();
+
+        assert_eq!(cmap.glyph_index('C'), expected);
+        assert_eq!(cmap.mappings().next().is_some(), expected.is_some());
+
+        records.pop();
+    }
+}
+
+#[test]
+fn fixture_mappings_agree_with_lookups() {
+    for name in FIXTURES {
+        let font = Font::try_from_stream(&mut Cursor::new(fixture(name))).unwrap();
+        let cmap = font.cmap().unwrap();
+        let num_glyphs = font.maxp().unwrap().num_glyphs;
+        let mappings = cmap.mappings().collect::<BTreeMap<_, _>>();
+
+        assert!(mappings.len() > 200, "{name}");
+        assert_ne!(cmap.glyph_index('A'), None);
+
+        for (&code_point, &glyph_id) in &mappings {
+            let c = char::from_u32(code_point).unwrap();
+            assert_eq!(
+                cmap.glyph_index(c),
+                Some(glyph_id),
+                "{name}: {code_point:#x}"
+            );
+            assert!(glyph_id < num_glyphs);
+        }
+
+        for (glyph_id, code_points) in cmap.reverse_mappings() {
+            assert!(code_points.iter().all(|c| mappings[c] == glyph_id));
+        }
+    }
+}
+
+#[test]
+fn looks_up_each_subtable_format() {
+    let cmap = corpus_cmap();
+    let subtables = cmap.cmap_subtables.as_slice();
+    let lookup = |index: usize, code_point: u32| subtables[index].glyph_index(code_point);
+
+    let cases = [
+        // format 0 maps each byte to its value modulo 5
+        (4, 0x42, Some(1)),
+        (4, 0xFE, Some(4)),
+        (4, 0x41, None),
+        (4, 0x100, None),
+        // format 2 mixes single bytes with two byte codes behind 0x81 and 0x82
+        (5, 0x41, Some(1)),
+        (5, 0x40, None),
+        (5, 0x81, None),
+        (5, 0x8145, Some(3)),
+        (5, 0x8286, Some(1)),
+        (5, 0x8244, None),
+        (5, 0x8345, None),
+        // format 8 covers 'A'..='Z' and a supplementary range
+        (6, 0x43, Some(3)),
+        (6, 0x10005, Some(7)),
+        (6, 0x40, None),
+        // format 10 is a trimmed array from U+10000
+        (8, 0x10000, Some(1)),
+        (8, 0x10004, Some(5)),
+        (8, 0x10005, None),
+        (8, 0xFFFF, None),
+        // format 13 maps whole ranges to one glyph
+        (3, 0x41, Some(3)),
+        (3, 0x1F64F, Some(1)),
+        (3, 0xE0080, Some(2)),
+        (3, 0x7F, None),
+    ];
+
+    for (index, code_point, expected) in cases {
+        assert_eq!(lookup(index, code_point), expected, "{code_point:#x}");
+    }
+
+    for subtable in &subtables[3..] {
+        for (code_point, glyph_id) in subtable.mappings() {
+            assert_eq!(subtable.glyph_index(code_point), Some(glyph_id));
+        }
+    }
+}
+
+#[test]
+fn resolves_variation_sequences() {
+    let cmap = corpus_cmap();
+    let CmapSubtable::Format14(variants) = &cmap.cmap_subtables.as_slice()[2] else {
+        panic!("expected the format 14 subtable");
+    };
+
+    assert_eq!(
+        variants.variant_glyph('\u{4E00}', '\u{FE00}'),
+        Some(VariantGlyph::Default)
+    );
+    assert_eq!(
+        variants.variant_glyph('2', '\u{FE01}'),
+        Some(VariantGlyph::Glyph(3))
+    );
+    assert_eq!(variants.variant_glyph('3', '\u{FE01}'), None);
+    assert_eq!(variants.variant_glyph('1', '\u{FE02}'), None);
+
+    // default sequences fall back to the regular mapping
+    assert_eq!(cmap.variant_glyph('5', '\u{FE00}'), Some(24));
+    assert_eq!(cmap.variant_glyph('\u{4E00}', '\u{FE00}'), None);
+    assert_eq!(cmap.variant_glyph('1', '\u{FE01}'), Some(2));
+    assert_eq!(cmap.variant_glyph('\u{9089}', '\u{E0100}'), Some(1));
+    assert_eq!(cmap.variant_glyph('\u{8FB0}', '\u{E0100}'), None);
+}
+
+#[test]
+fn writes_counts_from_edited_subtables() {
+    let mut cmap = corpus_cmap();
+
+    let subtables = std::mem::take(&mut cmap.cmap_subtables).into_vec();
+    cmap.cmap_subtables = subtables.into_iter().map(trim).collect();
+
+    let bytes = encode_to_vec(&cmap).unwrap();
+    let cmap = Cmap::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+    let subtables = cmap.cmap_subtables.as_slice();
+
+    assert_eq!(subtables[3].mappings().count(), 95);
+    assert_eq!(cmap.variant_glyph('1', '\u{FE01}'), None);
+    assert_eq!(cmap.variant_glyph('2', '\u{FE01}'), Some(3));
+    assert_eq!(cmap.variant_glyph('\u{9089}', '\u{E0100}'), None);
+}
+
+// keeps the first format 13 group and drops the first glyph of every non-default UVS
+fn trim(subtable: CmapSubtable) -> CmapSubtable {
+    match subtable {
+        CmapSubtable::Format13(mut table) => {
+            let groups = std::mem::take(&mut table.groups).into_vec();
+            table.groups = groups.into_iter().take(1).collect();
+            CmapSubtable::Format13(table)
+        }
+        CmapSubtable::Format14(mut table) => {
+            let selectors = std::mem::take(&mut table.var_selector_records).into_vec();
+            table.var_selector_records = selectors
+                .into_iter()
+                .map(|mut selector| {
+                    if let Opt::Some(uvs) = &mut selector.non_default_uvs {
+                        let mappings = std::mem::take(&mut uvs.uvs_mappings).into_vec();
+                        uvs.uvs_mappings = mappings.into_iter().skip(1).collect();
+                    }
+
+                    selector
+                })
+                .collect();
+            CmapSubtable::Format14(table)
+        }
+        subtable => subtable,
+    }
+}
+
+fn clone_record(record: &EncodingSubtable) -> EncodingSubtable {
+    EncodingSubtable::new(
+        record.platform_id,
+        record.platform_specific_id,
+        record.subtable_index,
+    )
+}
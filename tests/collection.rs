@@ -0,0 +1,107 @@
+mod common;
+
+use common::fixture;
+use fontori::{
+    table::tags,
+    ttf::{
+        checksum::ChecksumMismatch, collection::FontCollection, collection_dir::TtcHeader,
+        font::Font, font_ref::FontRef,
+    },
+    utils::{bincode::encode_to_vec, reader::TryFromStream},
+};
+use std::{collections::BTreeMap, io::Cursor};
+
+const FIXTURES: [&str; 2] = ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"];
+
+fn fixture_font(name: &str) -> Font {
+    Font::try_from_stream(&mut Cursor::new(fixture(name))).unwrap()
+}
+
+// the first font is repeated so every one of its tables has a second user
+fn collection(dsig: Option<Vec<u8>>) -> FontCollection {
+    let fonts = [FIXTURES[0], FIXTURES[1], FIXTURES[0]]
+        .map(fixture_font)
+        .into();
+
+    FontCollection { fonts, dsig }
+}
+
+#[test]
+fn round_trips_members() {
+    let bytes = encode_to_vec(collection(None)).unwrap();
+    let read = FontCollection::try_from_stream(&mut Cursor::new(&bytes)).unwrap();
+
+    assert_eq!(read.fonts.len(), 3);
+    assert!(read.dsig.is_none());
+
+    for (font, name) in read.fonts.iter().zip(FIXTURES.iter().cycle()) {
+        let expected = fixture_font(name).encode_tables().unwrap();
+        assert!(font.encode_tables().unwrap() == expected, "{name} changed");
+    }
+}
+
+#[test]
+fn shares_identical_tables() {
+    let bytes = encode_to_vec(collection(None)).unwrap();
+    let fonts = (0..3)
+        .map(|index| FontRef::from_collection(&bytes, index).unwrap())
+        .collect::<Vec<_>>();
+    let offsets = |font: &FontRef| {
+        let entries = font.font_directory().table_directory.iter();
+        entries
+            .map(|e| (e.tag, e.offset))
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    let first = offsets(&fonts[0]);
+
+    assert_eq!(offsets(&fonts[2]), first);
+    assert!(offsets(&fonts[1])
+        .iter()
+        .all(|(tag, offset)| first.get(tag) != Some(offset)));
+}
+
+#[test]
+fn balances_checksums_for_the_first_user_of_a_head() {
+    let mut trimmed = fixture_font(FIXTURES[0]);
+    trimmed.remove_table(tags::OS_2).unwrap().unwrap();
+
+    let mut collection = collection(None);
+    collection.fonts.push(trimmed);
+
+    let bytes = encode_to_vec(collection).unwrap();
+    let read = FontCollection::try_from_stream(&mut Cursor::new(&bytes)).unwrap();
+
+    for font in &read.fonts[..3] {
+        assert_eq!(font.verify_checksums().unwrap(), []);
+    }
+
+    // the trimmed font shares its head with the first member, whose checksum it already balances
+    let mismatches = read.fonts[3].verify_checksums().unwrap();
+    assert!(matches!(
+        mismatches.as_slice(),
+        [ChecksumMismatch::Adjustment { .. }]
+    ));
+}
+
+#[test]
+fn round_trips_the_dsig_header() {
+    let dsig = vec![0, 0, 0, 1, 0, 0, 0, 0, 0xAB];
+    let bytes = encode_to_vec(collection(Some(dsig.clone()))).unwrap();
+    let header = TtcHeader::try_from_stream(&mut Cursor::new(&bytes)).unwrap();
+    let dsig_header = header.dsig.as_option().unwrap();
+
+    assert_eq!((header.major_version, header.minor_version), (2, 0));
+    assert_eq!(dsig_header.dsig_tag, tags::DSIG);
+    assert_eq!(dsig_header.dsig_length, dsig.len() as u32);
+    assert_eq!(dsig_header.dsig_offset % 4, 0);
+
+    let read = FontCollection::try_from_stream(&mut Cursor::new(&bytes)).unwrap();
+    assert_eq!(read.dsig, Some(dsig));
+    assert_eq!(read.fonts.len(), 3);
+
+    let without = encode_to_vec(collection(None)).unwrap();
+    let header = TtcHeader::try_from_stream(&mut Cursor::new(&without)).unwrap();
+    assert_eq!(header.major_version, 1);
+    assert!(header.dsig.as_option().is_none());
+}
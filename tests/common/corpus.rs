@@ -0,0 +1,868 @@
+use fontori::table::tags::{self, Tag};
+
+// bits of a component's flags that are stored as-is, everything except the argument and scale
+// layout bits that decide which fields follow
+const COMPONENT_FLAG_BITS: [u16; 8] = [8, 0, 1, 2, 9, 10, 11, 12];
+// odd so that instructions are flagged on some components of a glyph but not on its last one
+const COMPONENTS_PER_GLYPH: usize = 7;
+
+type DefaultRanges = &'static [(u32, u8)];
+type GlyphMappings = &'static [(u32, u16)];
+const SCALE_KINDS: [u16; 4] = [0, 1 << 3, 1 << 6, 1 << 7];
+const MORE_COMPONENTS: u16 = 1 << 5;
+const WE_HAVE_INSTRUCTIONS: u16 = 1 << 8;
+
+#[derive(Default)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+    pub fn u8(&mut self, value: u8) -> &mut Self {
+        self.0.push(value);
+        self
+    }
+
+    pub fn u16(&mut self, value: u16) -> &mut Self {
+        self.0.extend(value.to_be_bytes());
+        self
+    }
+
+    pub fn i16(&mut self, value: i16) -> &mut Self {
+        self.0.extend(value.to_be_bytes());
+        self
+    }
+
+    pub fn u24(&mut self, value: u32) -> &mut Self {
+        self.0.extend(&value.to_be_bytes()[1..]);
+        self
+    }
+
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        self.0.extend(value.to_be_bytes());
+        self
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.0.extend(bytes);
+        self
+    }
+}
+
+pub struct Outline {
+    pub contours: Vec<Vec<(i16, i16, bool)>>,
+    pub instructions: Vec<u8>,
+    pub long_coordinates: bool,
+    pub repeat_flags: bool,
+    pub overlap: bool,
+}
+
+impl Outline {
+    pub fn new(contours: Vec<Vec<(i16, i16, bool)>>) -> Self {
+        Self {
+            contours,
+            instructions: Vec::new(),
+            long_coordinates: false,
+            repeat_flags: true,
+            overlap: false,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let points = self.contours.iter().flatten().collect::<Vec<_>>();
+        let mut flags = Vec::new();
+        let mut xs = Bytes::default();
+        let mut ys = Bytes::default();
+        let mut last = (0i16, 0i16);
+
+        for &&(x, y, on_curve) in &points {
+            let mut flag = on_curve as u8;
+            flag |= self.delta(x - last.0, &mut xs, 1, 4);
+            flag |= self.delta(y - last.1, &mut ys, 2, 5);
+            flags.push(flag);
+            last = (x, y);
+        }
+
+        if let Some(flag) = flags.first_mut().filter(|_| self.overlap) {
+            *flag |= 1 << 6;
+        }
+
+        let mut glyph = Bytes::default();
+        glyph.i16(self.contours.len() as i16);
+
+        for value in bounds(points.iter().map(|&&(x, y, _)| (x, y))) {
+            glyph.i16(value);
+        }
+
+        let mut end = 0;
+
+        for contour in &self.contours {
+            end += contour.len();
+            glyph.u16(end as u16 - 1);
+        }
+
+        glyph.u16(self.instructions.len() as u16);
+        glyph.bytes(&self.instructions);
+
+        let mut i = 0;
+
+        while i < flags.len() {
+            let run = flags[i..]
+                .iter()
+                .take_while(|&&f| f == flags[i])
+                .count()
+                .min(256);
+
+            // even a single repeat is legal, and costs the same as writing the flag twice
+            if self.repeat_flags && run > 1 {
+                glyph.u8(flags[i] | 1 << 3).u8((run - 1) as u8);
+            } else {
+                glyph.u8(flags[i]);
+            }
+
+            i += if self.repeat_flags { run } else { 1 };
+        }
+
+        glyph.bytes(&xs.0).bytes(&ys.0);
+        glyph.0
+    }
+
+    fn delta(&self, delta: i16, coordinates: &mut Bytes, short: u8, same: u8) -> u8 {
+        match delta {
+            _ if self.long_coordinates => {
+                coordinates.i16(delta);
+                0
+            }
+            0 => 1 << same,
+            -255..=255 => {
+                coordinates.u8(delta.unsigned_abs() as u8);
+                1 << short | ((delta > 0) as u8) << same
+            }
+            _ => {
+                coordinates.i16(delta);
+                0
+            }
+        }
+    }
+}
+
+fn bounds(points: impl Iterator<Item = (i16, i16)>) -> [i16; 4] {
+    points.fold([i16::MAX, i16::MAX, i16::MIN, i16::MIN], |b, (x, y)| {
+        [b[0].min(x), b[1].min(y), b[2].max(x), b[3].max(y)]
+    })
+}
+
+pub fn simple_glyphs() -> Vec<Vec<u8>> {
+    let square = vec![
+        (0, 0, true),
+        (0, 700, true),
+        (500, 700, true),
+        (500, 0, true),
+    ];
+    let curve = vec![
+        (-40, -300, true),
+        (-40, 20, false),
+        (300, 20, false),
+        (300, -300, true),
+        (1200, -300, false),
+        (1200, 900, true),
+    ];
+    let mut glyphs = vec![Outline::new(vec![square.clone()])];
+
+    glyphs.push(Outline {
+        instructions: vec![0xB0, 0x01, 0x2F],
+        overlap: true,
+        ..Outline::new(vec![square.clone(), curve.clone()])
+    });
+    glyphs.push(Outline {
+        long_coordinates: true,
+        ..Outline::new(vec![curve.clone()])
+    });
+    glyphs.push(Outline {
+        repeat_flags: false,
+        instructions: vec![0x40; 5],
+        ..Outline::new(vec![curve, square])
+    });
+    glyphs.push(Outline::new(vec![(0..300)
+        .map(|i| (i * 3, (i % 7) * 90, i % 5 != 0))
+        .collect()]));
+    glyphs.push(Outline::new(vec![vec![(7, 7, true)]]));
+
+    glyphs.iter().map(Outline::encode).collect()
+}
+
+pub fn compound_glyphs(components: u16) -> Vec<Vec<u8>> {
+    let mut flags = Vec::new();
+
+    for scale in SCALE_KINDS {
+        for bits in 0..1u16 << COMPONENT_FLAG_BITS.len() {
+            let set = COMPONENT_FLAG_BITS
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| bits & 1 << i != 0)
+                .fold(scale, |flags, (_, bit)| flags | 1 << bit);
+
+            flags.push(set);
+        }
+    }
+
+    // reserved bits are carried through untouched
+    flags.push(1 << 4 | 0xE000);
+
+    flags
+        .chunks(COMPONENTS_PER_GLYPH)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut glyph = Bytes::default();
+            glyph.i16(-1).i16(-100).i16(-200).i16(900).i16(800);
+
+            for (j, &flag) in chunk.iter().enumerate() {
+                let more = if j + 1 < chunk.len() {
+                    MORE_COMPONENTS
+                } else {
+                    0
+                };
+                let n = (i * COMPONENTS_PER_GLYPH + j) as i16;
+
+                glyph.u16(flag | more).u16((i + j) as u16 % components);
+
+                if flag & 1 != 0 {
+                    glyph.i16(n * 17 - 9000).i16(-n);
+                } else {
+                    glyph.u8((n % 256) as u8).u8(((n * 7) % 256) as u8);
+                }
+
+                let scales = match flag & (1 << 3 | 1 << 6 | 1 << 7) {
+                    0x08 => 1,
+                    0x40 => 2,
+                    0x80 => 4,
+                    _ => 0,
+                };
+
+                for k in 0..scales {
+                    glyph.i16(0x4000 - n * 11 + k);
+                }
+            }
+
+            if chunk.iter().any(|f| f & WE_HAVE_INSTRUCTIONS != 0) {
+                glyph.u16(3).bytes(&[0xB0, i as u8, 0x2F]);
+            }
+
+            glyph.0
+        })
+        .collect()
+}
+
+pub fn cmap() -> Vec<u8> {
+    let format_4 = {
+        let segments = [(0x20u16, 0x7Eu16, -29i16, false), (0x100, 0x10F, 0, true)];
+        let seg_count = segments.len() as u16 + 1;
+        let mut table = Bytes::default();
+        table.u16(4).u16(16 + 8 * seg_count + 32).u16(0);
+        table
+            .u16(seg_count * 2)
+            .u16(4)
+            .u16(1)
+            .u16(seg_count * 2 - 4);
+
+        for (_, end, _, _) in segments {
+            table.u16(end);
+        }
+
+        table.u16(0xFFFF).u16(0);
+
+        for (start, _, _, _) in segments {
+            table.u16(start);
+        }
+
+        table.u16(0xFFFF);
+
+        for (_, _, delta, _) in segments {
+            table.i16(delta);
+        }
+
+        table.i16(1);
+
+        for (i, (_, _, _, indexed)) in segments.iter().enumerate() {
+            let offset = (seg_count as usize - i) * 2;
+            table.u16(if *indexed { offset as u16 } else { 0 });
+        }
+
+        table.u16(0);
+
+        for glyph in 0..16 {
+            table.u16(glyph % 3 + 1);
+        }
+
+        table.0
+    };
+
+    let format_12 = |format: u16| {
+        let groups = [
+            (0x20u32, 0x7Eu32, 3u32),
+            (0x1F600, 0x1F64F, 1),
+            (0xE0000, 0xE00FF, 2),
+        ];
+        let mut table = Bytes::default();
+        table
+            .u16(format)
+            .u16(0)
+            .u32(16 + 12 * groups.len() as u32)
+            .u32(0);
+        table.u32(groups.len() as u32);
+
+        for (start, end, glyph) in groups {
+            table.u32(start).u32(end).u32(glyph);
+        }
+
+        table.0
+    };
+
+    let format_0 = {
+        let mut table = Bytes::default();
+        table.u16(0).u16(262).u16(0);
+
+        for code in 0..=255u8 {
+            table.u8(code % 5);
+        }
+
+        table.0
+    };
+
+    let format_2 = {
+        let mut table = Bytes::default();
+        let array_start = 6 + 512 + 3 * 8;
+        table.u16(2).u16(array_start + (256 + 32) * 2).u16(0);
+
+        for high in 0..=255u16 {
+            table.u16(match high {
+                0x81 => 8,
+                0x82 => 16,
+                _ => 0,
+            });
+        }
+
+        for (i, (first, count, delta, index)) in [
+            (0u16, 256u16, 0i16, 0u16),
+            (0x40, 16, 2, 256),
+            (0x80, 16, -1, 272),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let range_offset = array_start + index * 2 - (6 + 512 + i as u16 * 8 + 6);
+            table.u16(first).u16(count).i16(delta).u16(range_offset);
+        }
+
+        for glyph in 0..256 + 32 {
+            table.u16(glyph % 4);
+        }
+
+        table.0
+    };
+
+    let format_6 = {
+        let mut table = Bytes::default();
+        table.u16(6).u16(10 + 20).u16(0).u16(0x41).u16(10);
+
+        for glyph in 0..10 {
+            table.u16(glyph % 4);
+        }
+
+        table.0
+    };
+
+    let format_8 = {
+        let mut table = Bytes::default();
+        table.u16(8).u16(0).u32(16 + 8192 + 24).u32(0);
+
+        for i in 0..8192 {
+            table.u8(if i == 0xD8 >> 3 { 0xFF } else { 0 });
+        }
+
+        table.u32(2).u32(0x41).u32(0x5A).u32(1);
+        table.u32(0x10000).u32(0x10010).u32(2);
+        table.0
+    };
+
+    let format_10 = {
+        let mut table = Bytes::default();
+        table.u16(10).u16(0).u32(20 + 10).u32(0).u32(0x10000).u32(5);
+
+        for glyph in 0..5 {
+            table.u16(glyph + 1);
+        }
+
+        table.0
+    };
+
+    let format_14 = {
+        let selectors: [(u32, DefaultRanges, GlyphMappings); 3] = [
+            (0xFE00, &[(0x30, 9), (0x4E00, 0)], &[]),
+            (0xFE01, &[], &[(0x31, 2), (0x32, 3)]),
+            (0xE0100, &[(0x8FAE, 2)], &[(0x9089, 1)]),
+        ];
+        let mut records = Bytes::default();
+        let mut data = Bytes::default();
+        let data_start = 10 + 11 * selectors.len() as u32;
+
+        for (selector, defaults, glyphs) in selectors {
+            records.u24(selector);
+
+            match defaults.is_empty() {
+                true => records.u32(0),
+                false => records.u32(data_start + data.0.len() as u32),
+            };
+
+            if !defaults.is_empty() {
+                data.u32(defaults.len() as u32);
+
+                for &(start, additional) in defaults {
+                    data.u24(start).u8(additional);
+                }
+            }
+
+            match glyphs.is_empty() {
+                true => records.u32(0),
+                false => records.u32(data_start + data.0.len() as u32),
+            };
+
+            if !glyphs.is_empty() {
+                data.u32(glyphs.len() as u32);
+
+                for &(code_point, glyph) in glyphs {
+                    data.u24(code_point).u16(glyph);
+                }
+            }
+        }
+
+        let mut table = Bytes::default();
+        table.u16(14).u32(data_start + data.0.len() as u32);
+        table.u32(selectors.len() as u32);
+        table.bytes(&records.0).bytes(&data.0);
+        table.0
+    };
+
+    let subtables = [
+        format_4,
+        format_12(12),
+        format_14,
+        format_12(13),
+        format_0,
+        format_2,
+        format_8,
+        format_6,
+        format_10,
+    ];
+    let records = [
+        (0, 3, 0),
+        (0, 4, 1),
+        (0, 5, 2),
+        (0, 6, 3),
+        (1, 0, 4),
+        (3, 1, 0),
+        (3, 2, 5),
+        (3, 10, 1),
+        (3, 12, 6),
+        (4, 0, 7),
+        (4, 1, 8),
+    ];
+    let mut offsets = Vec::new();
+    let mut offset = 4 + 8 * records.len();
+
+    for subtable in &subtables {
+        offsets.push(offset as u32);
+        offset += subtable.len();
+    }
+
+    let mut table = Bytes::default();
+    table.u16(0).u16(records.len() as u16);
+
+    for (platform, encoding, index) in records {
+        table.u16(platform).u16(encoding).u32(offsets[index]);
+    }
+
+    for subtable in &subtables {
+        table.bytes(subtable);
+    }
+
+    table.0
+}
+
+pub fn minimal_cmap() -> Vec<u8> {
+    let mut table = Bytes::default();
+    table.u16(0).u16(1).u16(3).u16(1).u32(12);
+    table.u16(4).u16(24).u16(0).u16(2).u16(2).u16(0).u16(0);
+    table.u16(0xFFFF).u16(0).u16(0xFFFF).i16(1).u16(0);
+    table.0
+}
+
+// strings are written in the order given, records may share them or point into them out of order
+pub fn name(format: u16, strings: &[&[u8]], records: &[(u16, u16, u16, u16, usize)]) -> Vec<u8> {
+    let lang_tags = if format == 1 { &strings[..1] } else { &[][..] };
+    let mut offsets = Vec::new();
+    let mut storage = Vec::new();
+
+    for string in strings {
+        offsets.push(storage.len() as u16);
+        storage.extend(*string);
+    }
+
+    let mut header_size = 6 + 12 * records.len();
+
+    if format == 1 {
+        header_size += 2 + 4 * lang_tags.len();
+    }
+
+    let mut table = Bytes::default();
+    table
+        .u16(format)
+        .u16(records.len() as u16)
+        .u16(header_size as u16);
+
+    for &(platform, encoding, language, name_id, string) in records {
+        table.u16(platform).u16(encoding).u16(language).u16(name_id);
+        table.u16(strings[string].len() as u16).u16(offsets[string]);
+    }
+
+    if format == 1 {
+        table.u16(lang_tags.len() as u16);
+
+        for (i, tag) in lang_tags.iter().enumerate() {
+            table.u16(tag.len() as u16).u16(offsets[i]);
+        }
+    }
+
+    table.bytes(&storage);
+    table.0
+}
+
+pub fn post(version: u32, num_glyphs: u16) -> Vec<u8> {
+    let mut table = Bytes::default();
+    table.u32(version).u32(0xFFF4_8000).i16(-75).i16(50).u32(0);
+    table.u32(0).u32(0).u32(0).u32(0);
+
+    match version {
+        0x0002_0000 => {
+            table.u16(num_glyphs);
+
+            for glyph in 0..num_glyphs {
+                table.u16(if glyph % 3 == 0 {
+                    glyph
+                } else {
+                    258 + glyph / 3
+                });
+            }
+
+            for index in 0..=num_glyphs.saturating_sub(1) / 3 {
+                let name = format!("name{index}");
+                table.u8(name.len() as u8).bytes(name.as_bytes());
+            }
+        }
+        0x0002_5000 => {
+            table.u16(num_glyphs);
+
+            for glyph in 0..num_glyphs {
+                table.u8((glyph % 5) as u8);
+            }
+        }
+        _ => {}
+    }
+
+    table.0
+}
+
+pub fn os2(version: u16) -> Vec<u8> {
+    let mut table = Bytes::default();
+    table.u16(version).i16(480).u16(400).u16(5).u16(0x0008);
+
+    for value in [650, 600, 0, 75, 650, 600, 0, 350, 50, 300] {
+        table.i16(value);
+    }
+
+    table.i16(0x0801).bytes(&[2, 11, 6, 3, 3, 4, 2, 2, 4, 4]);
+    table
+        .u32(0x0000_0287)
+        .u32(0x0200_0000)
+        .u32(0)
+        .u32(0x8000_0000);
+    table.bytes(b"TEST").u16(0x00C0).u16(0x20).u16(0xFFFF);
+    table.i16(850).i16(-250).i16(100).u16(1050).u16(300);
+
+    if version >= 1 {
+        table.u32(0x2000_019F).u32(0xDFD7_0000);
+    }
+
+    if version >= 2 {
+        table.i16(500).i16(700).u16(0).u16(0x20).u16(3);
+    }
+
+    if version >= 5 {
+        table.u16(0).u16(0xFFFF);
+    }
+
+    table.0
+}
+
+pub fn vhea(num_long_metrics: u16) -> Vec<u8> {
+    let mut table = Bytes::default();
+    table.u32(0x0001_1000).i16(500).i16(-500).i16(0).u16(1000);
+    table.i16(100).i16(-60).i16(1100).i16(0).i16(1).i16(0);
+    table.bytes(&[0; 8]).i16(0).u16(num_long_metrics);
+    table.0
+}
+
+pub fn vmtx(num_glyphs: u16, num_long_metrics: u16) -> Vec<u8> {
+    let mut table = Bytes::default();
+
+    for glyph in 0..num_glyphs {
+        if glyph < num_long_metrics {
+            table.u16(1000 - glyph * 10);
+        }
+
+        table.i16(100 + glyph as i16);
+    }
+
+    table.0
+}
+
+pub struct FontSpec {
+    pub glyphs: Vec<Vec<u8>>,
+    pub long_loca: bool,
+    pub alignment: usize,
+    pub num_long_metrics: u16,
+    pub tables: Vec<(Tag, Vec<u8>)>,
+}
+
+impl FontSpec {
+    pub fn new(glyphs: Vec<Vec<u8>>) -> Self {
+        let num_glyphs = glyphs.len() as u16;
+        let name = name(
+            0,
+            &[b"Corpus", b"\0R\0e\0g\0u\0l\0a\0r"],
+            &[(1, 0, 0, 1, 0), (3, 1, 0x409, 2, 1)],
+        );
+
+        Self {
+            glyphs,
+            long_loca: false,
+            alignment: 4,
+            num_long_metrics: num_glyphs,
+            tables: vec![
+                (tags::CMAP, minimal_cmap()),
+                (tags::NAME, name),
+                (tags::POST, post(0x0003_0000, num_glyphs)),
+            ],
+        }
+    }
+
+    pub fn table(mut self, tag: Tag, table: Vec<u8>) -> Self {
+        self.tables.retain(|&(t, _)| t != tag);
+        self.tables.push((tag, table));
+        self
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let num_glyphs = self.glyphs.len() as u16;
+        let mut glyf = Vec::new();
+        let mut loca = Bytes::default();
+
+        for glyph in self.glyphs.iter().map(Some).chain([None]) {
+            match self.long_loca {
+                true => loca.u32(glyf.len() as u32),
+                false => loca.u16((glyf.len() / 2) as u16),
+            };
+
+            if let Some(glyph) = glyph {
+                glyf.extend(glyph);
+                glyf.resize(glyf.len().next_multiple_of(self.alignment), 0);
+            }
+        }
+
+        let mut head = Bytes::default();
+        head.u32(0x0001_0000)
+            .u32(0x0001_8000)
+            .u32(0)
+            .u32(0x5F0F_3CF5);
+        head.u16(0x000B)
+            .u16(1000)
+            .u32(0)
+            .u32(0xD1A2_B3C4)
+            .u32(0)
+            .u32(0xD1A2_B3C5);
+        head.i16(-50)
+            .i16(-300)
+            .i16(1200)
+            .i16(900)
+            .u16(0)
+            .u16(8)
+            .i16(2);
+        head.i16(self.long_loca as i16).i16(0);
+
+        let mut hhea = Bytes::default();
+        hhea.u32(0x0001_0000).i16(900).i16(-200).i16(50).u16(1200);
+        hhea.i16(-50).i16(-120).i16(1200).i16(1).i16(0).i16(0);
+        hhea.bytes(&[1, 2, 3, 4, 5, 6, 7, 8])
+            .i16(0)
+            .u16(self.num_long_metrics);
+
+        let mut maxp = Bytes::default();
+        maxp.u32(0x0001_0000).u16(num_glyphs);
+
+        for value in [300, 4, 24, 3, 2, 0, 16, 1, 0, 64, 5, 8, 2] {
+            maxp.u16(value);
+        }
+
+        let mut hmtx = Bytes::default();
+
+        for glyph in 0..num_glyphs {
+            if glyph < self.num_long_metrics {
+                hmtx.u16(500 + glyph * 10);
+            }
+
+            hmtx.i16(glyph as i16 - 3);
+        }
+
+        let mut tables = vec![
+            (tags::HEAD, head.0),
+            (tags::HHEA, hhea.0),
+            (tags::MAXP, maxp.0),
+            (tags::HMTX, hmtx.0),
+            (tags::LOCA, loca.0),
+            (tags::GLYF, glyf),
+        ];
+
+        tables.extend(self.tables.iter().cloned());
+        sfnt(&mut tables)
+    }
+}
+
+pub fn sfnt(tables: &mut [(Tag, Vec<u8>)]) -> Vec<u8> {
+    tables.sort_by_key(|&(tag, _)| tag);
+
+    let count = tables.len() as u16;
+    let selector = 15 - count.leading_zeros() as u16;
+    let mut font = Bytes::default();
+    font.u32(0x0001_0000)
+        .u16(count)
+        .u16(16 << selector)
+        .u16(selector);
+    font.u16(count * 16 - (16 << selector));
+
+    let mut offset = 12 + 16 * tables.len();
+
+    for (tag, table) in tables.iter() {
+        let checksum = table
+            .chunks(4)
+            .map(|c| {
+                let mut word = [0; 4];
+                word[..c.len()].copy_from_slice(c);
+                u32::from_be_bytes(word)
+            })
+            .fold(0u32, u32::wrapping_add);
+
+        font.u32(*tag)
+            .u32(checksum)
+            .u32(offset as u32)
+            .u32(table.len() as u32);
+        offset += table.len().next_multiple_of(4);
+    }
+
+    for (_, table) in tables.iter() {
+        font.bytes(table);
+        font.0.resize(font.0.len().next_multiple_of(4), 0);
+    }
+
+    font.0
+}
+
+pub fn fonts() -> Vec<(&'static str, Vec<u8>)> {
+    let simple = simple_glyphs();
+    let num_simple = simple.len() as u16;
+    let mut mixed = simple.clone();
+    mixed.insert(1, Vec::new());
+    mixed.extend(compound_glyphs(num_simple + 1));
+
+    let specs = [
+        (
+            "short loca, 2 byte padding",
+            FontSpec {
+                alignment: 2,
+                num_long_metrics: 3,
+                ..FontSpec::new(simple.clone())
+            }
+            .table(tags::OS_2, os2(0))
+            .table(tags::POST, post(0x0001_0000, num_simple)),
+        ),
+        (
+            "long loca, no padding",
+            FontSpec {
+                long_loca: true,
+                alignment: 1,
+                num_long_metrics: 1,
+                ..FontSpec::new(mixed.clone())
+            }
+            .table(tags::OS_2, os2(1))
+            .table(tags::POST, post(0x0002_0000, mixed.len() as u16)),
+        ),
+        (
+            "long loca, 4 byte padding",
+            FontSpec {
+                long_loca: true,
+                ..FontSpec::new(mixed.clone())
+            }
+            .table(tags::CMAP, cmap())
+            .table(tags::OS_2, os2(2))
+            .table(tags::POST, post(0x0002_5000, mixed.len() as u16)),
+        ),
+        (
+            "short loca, 4 byte padding",
+            FontSpec::new(mixed.clone())
+                .table(tags::OS_2, os2(3))
+                .table(
+                    tags::NAME,
+                    name(
+                        1,
+                        &[b"en-US", b"Shared", b"Family", b"\0F\0a\0m"],
+                        &[
+                            (1, 0, 0, 1, 2),
+                            (1, 0, 0, 4, 2),
+                            (1, 0, 0, 16, 1),
+                            (3, 1, 0x8000, 1, 3),
+                        ],
+                    ),
+                ),
+        ),
+        (
+            "names out of storage order",
+            FontSpec::new(simple.clone())
+                .table(tags::OS_2, os2(4))
+                .table(
+                    tags::NAME,
+                    name(
+                        0,
+                        &[b"Second", b"First", b"Sec"],
+                        &[
+                            (1, 0, 0, 1, 1),
+                            (1, 0, 0, 2, 0),
+                            (1, 0, 0, 3, 2),
+                            (1, 0, 0, 5, 0),
+                        ],
+                    ),
+                ),
+        ),
+        (
+            "vertical metrics",
+            FontSpec::new(mixed.clone())
+                .table(tags::VHEA, vhea(4))
+                .table(tags::VMTX, vmtx(mixed.len() as u16, 4)),
+        ),
+        (
+            "OS/2 version 5",
+            FontSpec::new(simple).table(tags::OS_2, os2(5)),
+        ),
+    ];
+
+    specs
+        .into_iter()
+        .map(|(name, spec)| (name, spec.build()))
+        .collect()
+}
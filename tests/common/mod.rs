@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+pub mod corpus;
+
+use fontori::{
+    table::{glyph::ResolvedGlyph, GetFontTable},
+    ttf::font::Font,
+};
+use std::path::Path;
+
+pub fn fixture(name: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fonts")
+        .join(name);
+
+    std::fs::read(path).unwrap()
+}
+
+pub fn outlines(font: &Font) -> Vec<ResolvedGlyph> {
+    let glyf = font.glyf().unwrap();
+    let maxp = font.maxp().unwrap();
+
+    (0..maxp.num_glyphs)
+        .map(|glyph_id| glyf.resolve(glyph_id, maxp).unwrap())
+        .collect()
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use common::fixture;
+use fontori::{
+    table::{GetFontTable, LongHorMetric},
+    ttf::{font::Font, font_ref::FontRef},
+    utils::{bincode::encode_to_vec, reader::TryFromStream},
+};
+use std::{collections::BTreeMap, io::Cursor};
+
+const FIXTURES: [&str; 2] = ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"];
+
+// the fixtures cover the basic plane and a little beyond
+const LAST_CODE_POINT: u32 = 0x2FFFF;
+
+fn fonts(name: &str) -> (Font, Vec<u8>) {
+    let woff2 = Font::try_from_stream(&mut Cursor::new(fixture(name))).unwrap();
+    let (_, sfnt) = woff2.encode_sfnt().unwrap();
+    let font = Font::try_from_stream(&mut Cursor::new(&sfnt)).unwrap();
+
+    (font, sfnt)
+}
+
+fn metric(metric: LongHorMetric) -> (u16, i16) {
+    (metric.advance_width, metric.left_side_bearing)
+}
+
+#[test]
+fn decodes_the_same_headers() {
+    for name in FIXTURES {
+        let (font, sfnt) = fonts(name);
+        let font_ref = FontRef::new(&sfnt).unwrap();
+
+        assert_eq!(
+            encode_to_vec(font_ref.head().unwrap()).unwrap(),
+            encode_to_vec(font.head().unwrap()).unwrap()
+        );
+        assert_eq!(
+            encode_to_vec(font_ref.hhea().unwrap()).unwrap(),
+            encode_to_vec(font.hhea().unwrap()).unwrap()
+        );
+        assert_eq!(
+            encode_to_vec(font_ref.maxp().unwrap()).unwrap(),
+            encode_to_vec(font.maxp().unwrap()).unwrap()
+        );
+    }
+}
+
+#[test]
+fn cmap_ref_matches_every_code_point() {
+    for name in FIXTURES {
+        let (font, sfnt) = fonts(name);
+        let cmap = font.cmap().unwrap();
+        let cmap_ref = FontRef::new(&sfnt).unwrap().cmap().unwrap();
+
+        for c in (0..=LAST_CODE_POINT).filter_map(char::from_u32) {
+            assert_eq!(
+                cmap_ref.glyph_index(c),
+                cmap.glyph_index(c),
+                "{name}: {c:?}"
+            );
+        }
+
+        let decoded = cmap_ref.best_subtable().unwrap().decode().unwrap();
+        assert_eq!(
+            decoded.mappings().collect::<BTreeMap<_, _>>(),
+            cmap.mappings().collect::<BTreeMap<_, _>>()
+        );
+    }
+}
+
+#[test]
+fn loca_and_glyf_refs_match_every_glyph() {
+    for name in FIXTURES {
+        let (font, sfnt) = fonts(name);
+        let font_ref = FontRef::new(&sfnt).unwrap();
+        let loca = font.loca().unwrap();
+        let loca_ref = font_ref.loca().unwrap();
+        let glyf = font.glyf().unwrap();
+        let glyf_ref = font_ref.glyf().unwrap();
+        let offsets = loca.offsets.as_slice();
+
+        assert_eq!(loca_ref.format(), loca.format);
+        assert_eq!(loca_ref.len(), offsets.len());
+        assert_eq!(glyf_ref.num_glyphs(), glyf.num_glyphs());
+
+        for (index, &offset) in offsets.iter().enumerate() {
+            assert_eq!(loca_ref.offset(index), Some(offset));
+        }
+
+        for glyph_id in 0..glyf.num_glyphs() as u16 {
+            let owned = glyf.get(glyph_id);
+            let borrowed = glyf_ref.glyph(glyph_id).unwrap();
+
+            assert_eq!(
+                borrowed.as_ref().map(|g| encode_to_vec(g).unwrap()),
+                owned.map(|g| encode_to_vec(g).unwrap()),
+                "{name}: glyph {glyph_id}"
+            );
+            assert_eq!(
+                glyf_ref.bounds(glyph_id),
+                owned.map(|g| [
+                    g.header.x_min,
+                    g.header.y_min,
+                    g.header.x_max,
+                    g.header.y_max
+                ])
+            );
+        }
+    }
+}
+
+#[test]
+fn hmtx_ref_matches_every_glyph() {
+    for name in FIXTURES {
+        let (font, sfnt) = fonts(name);
+        let hmtx = font.hmtx().unwrap();
+        let hmtx_ref = FontRef::new(&sfnt).unwrap().hmtx().unwrap();
+        let num_glyphs = font.maxp().unwrap().num_glyphs;
+
+        for glyph_id in 0..num_glyphs {
+            assert_eq!(
+                hmtx_ref.metric(glyph_id).map(metric),
+                hmtx.metric(glyph_id).map(metric)
+            );
+            assert_eq!(
+                hmtx_ref.advance_width(glyph_id),
+                font.advance_width(glyph_id).ok()
+            );
+            assert_eq!(
+                hmtx_ref.left_side_bearing(glyph_id),
+                font.left_side_bearing(glyph_id).ok()
+            );
+        }
+
+        assert!(hmtx_ref.metric(num_glyphs).is_none());
+    }
+}
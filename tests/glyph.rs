@@ -0,0 +1,121 @@
+mod common;
+
+use common::fixture;
+use fontori::{
+    table::{
+        glyph::{glyph_stats, GlyphData, Point, SimpleGlyph},
+        GetFontTable,
+    },
+    ttf::font::Font,
+    utils::{bincode::encode_to_vec, reader::TryFromStream},
+};
+use std::io::Cursor;
+
+const FIXTURES: [&str; 2] = ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"];
+
+fn point(x: i16, y: i16) -> Point {
+    Point {
+        x,
+        y,
+        on_curve: true,
+    }
+}
+
+// points along the x axis, each one the given distance from the last
+fn line(deltas: &[i16]) -> Vec<Point> {
+    let mut x = 0i16;
+    let mut points = vec![point(0, 0)];
+
+    for &dx in deltas {
+        x = x.wrapping_add(dx);
+        points.push(point(x, 0));
+    }
+
+    points
+}
+
+#[test]
+fn rebuilds_fixture_glyphs_from_their_points() {
+    for name in FIXTURES {
+        let font = Font::try_from_stream(&mut Cursor::new(fixture(name))).unwrap();
+
+        for glyph in font.glyf().unwrap().glyphs.iter() {
+            let Some(GlyphData::Simple(simple)) = glyph.as_option().map(|g| &g.data) else {
+                continue;
+            };
+
+            let instructions = simple.instructions.as_slice().to_vec();
+            let rebuilt = SimpleGlyph::from_contours(&simple.contours(), instructions);
+
+            assert_eq!(rebuilt.points(), simple.points());
+            assert_eq!(
+                rebuilt.end_pts_of_contours.as_slice(),
+                simple.end_pts_of_contours.as_slice()
+            );
+            assert!(encode_to_vec(&rebuilt).unwrap().len() <= encode_to_vec(simple).unwrap().len());
+        }
+    }
+}
+
+#[test]
+fn skips_empty_contours() {
+    let square = vec![point(0, 0), point(0, 10), point(10, 10), point(10, 0)];
+    let glyph = SimpleGlyph::from_contours(&[vec![], square.clone(), vec![]], vec![]);
+
+    assert_eq!(glyph.end_pts_of_contours.as_slice(), [3]);
+    assert_eq!(glyph.contours(), [square]);
+    assert_eq!(SimpleGlyph::from_contours(&[vec![]], vec![]).points(), []);
+}
+
+#[test]
+fn packs_each_delta_into_the_fewest_bytes() {
+    let glyph = SimpleGlyph::from_contours(&[line(&[0, 1, -1, 255, -255, 256, -256])], vec![]);
+
+    // zero deltas take no bytes, up to 255 either way one byte and anything further two
+    let sizes = [0, 0, 1, 1, 1, 1, 2, 2];
+    let x_size = encode_to_vec(&glyph.x_coordinates).unwrap().len();
+
+    assert_eq!(x_size, sizes.iter().sum::<usize>());
+    assert_eq!(encode_to_vec(&glyph.y_coordinates).unwrap(), []);
+    assert_eq!(glyph.points(), line(&[0, 1, -1, 255, -255, 256, -256]));
+}
+
+#[test]
+fn repeats_flags_only_when_it_saves_bytes() {
+    let cases = [
+        // the first point and a pair of equal flags are written out in full
+        (vec![10; 2], 3),
+        // three equal flags become one flag and a count
+        (vec![10; 3], 3),
+        (vec![10; 9], 3),
+        // a repeat count covers at most 255 further flags
+        (vec![10; 300], 5),
+        (vec![10, -10, 10, -10], 5),
+    ];
+
+    for (deltas, flag_bytes) in cases {
+        let glyph = SimpleGlyph::from_contours(&[line(&deltas)], vec![]);
+
+        assert_eq!(glyph.flags.as_slice().len(), flag_bytes, "{deltas:?}");
+        assert_eq!(glyph.points(), line(&deltas));
+    }
+}
+
+#[test]
+fn counts_glyph_stats_with_a_short_memo() {
+    let font = Font::try_from_stream(&mut Cursor::new(fixture(FIXTURES[0]))).unwrap();
+    let glyf = font.glyf().unwrap();
+    let num_glyphs = glyf.glyphs.as_slice().len();
+    let mut memo = vec![None; num_glyphs];
+    let mut short_memo = vec![None; num_glyphs / 2];
+
+    for glyph_id in 0..num_glyphs as u16 {
+        let stats = glyph_stats(glyf, glyph_id, &mut memo, &mut Vec::new()).unwrap();
+        let short = glyph_stats(glyf, glyph_id, &mut short_memo, &mut Vec::new()).unwrap();
+
+        assert_eq!(
+            (short.points, short.contours, short.depth),
+            (stats.points, stats.contours, stats.depth)
+        );
+    }
+}
@@ -0,0 +1,255 @@
+mod common;
+
+use common::{
+    corpus::{self, FontSpec},
+    fixture,
+};
+use fontori::{
+    error::Error,
+    table::{
+        glyph::Glyph,
+        tags::{self, Tag},
+        GetFontTable, Glyf, Maxp,
+    },
+    ttf::{font::Font, font_ref::FontRef},
+    utils::{
+        bincode::encode_to_vec,
+        reader::TryFromStream,
+        types::{Opt, Seq},
+    },
+    validate::validate,
+    woff::Woff,
+};
+use std::io::Cursor;
+
+const FIXTURES: [&str; 2] = ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"];
+
+fn parse<T: TryFromStream>(bytes: &[u8]) -> T {
+    T::try_from_stream(&mut Cursor::new(bytes)).unwrap()
+}
+
+fn sfnt(name: &str) -> Vec<u8> {
+    let font: Font = parse(&fixture(name));
+    font.encode_sfnt().unwrap().1
+}
+
+fn table_offset(bytes: &[u8], tag: Tag) -> u64 {
+    let font = FontRef::new(bytes).unwrap();
+    let entries = font.font_directory().table_directory.as_slice();
+    entries.iter().find(|e| e.tag == tag).unwrap().offset.into()
+}
+
+fn read_error(bytes: &[u8]) -> Error {
+    Font::try_from_stream(&mut Cursor::new(bytes)).unwrap_err()
+}
+
+fn inspect(bytes: &[u8]) {
+    let _ = Font::try_from_stream(&mut Cursor::new(bytes));
+
+    if let Ok(font) = FontRef::new(bytes) {
+        validate(&font);
+    }
+}
+
+#[test]
+fn rejects_truncated_fonts() {
+    for name in FIXTURES {
+        let bytes = sfnt(name);
+
+        for length in (0..bytes.len()).step_by(bytes.len() / 64) {
+            let truncated = &bytes[..length];
+            assert!(Font::try_from_stream(&mut Cursor::new(truncated)).is_err());
+            inspect(truncated);
+        }
+    }
+}
+
+#[test]
+fn survives_corrupted_bytes() {
+    let bytes = sfnt(FIXTURES[0]);
+    let mut state = 0x2545F491u32;
+
+    for _ in 0..64 {
+        let mut corrupted = bytes.clone();
+
+        for _ in 0..8 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+
+            // most of the interesting structure sits in the directory and the small tables up front
+            let position = state as usize % corrupted.len().min(4096);
+            corrupted[position] = (state >> 24) as u8;
+        }
+
+        inspect(&corrupted);
+    }
+}
+
+#[test]
+fn rejects_oversized_woff_tables() {
+    let font: Font = parse(&fixture(FIXTURES[0]));
+    let mut bytes = encode_to_vec(Woff::new(font)).unwrap();
+
+    // origLength of the first table directory entry, after the 44 byte header
+    bytes[56..60].copy_from_slice(&u32::MAX.to_be_bytes());
+
+    let result = Woff::try_from_stream(&mut Cursor::new(bytes));
+    assert!(matches!(result, Err(Error::DataTooLarge(_))));
+}
+
+#[test]
+fn limits_shared_component_expansion() {
+    let font: Font = parse(&fixture(FIXTURES[0]));
+    let maxp = Maxp {
+        max_component_depth: 64,
+        ..*font.maxp().unwrap()
+    };
+
+    let mut simple = vec![0, 1, 0, 0, 0, 0, 0, 10, 0, 10, 0, 2, 0, 0, 1, 1, 1];
+    simple.extend([0i16, 10, 5, 0, 0, 10].iter().flat_map(|v| v.to_be_bytes()));
+
+    let mut glyphs = vec![Opt::Some(parse::<Glyph>(&simple))];
+
+    // each glyph places the previous one twice, doubling the point count at every level
+    for glyph_id in 0..48u16 {
+        let mut compound = vec![0xFF, 0xFF, 0, 0, 0, 0, 0, 10, 0, 10];
+
+        for (flags, dx) in [(0x23u16, 0i16), (0x03, 3)] {
+            compound.extend(flags.to_be_bytes());
+            compound.extend(glyph_id.to_be_bytes());
+            compound.extend(dx.to_be_bytes());
+            compound.extend(0i16.to_be_bytes());
+        }
+
+        glyphs.push(Opt::Some(parse::<Glyph>(&compound)));
+    }
+
+    let glyf = Glyf::new(Seq::from(glyphs));
+
+    assert_eq!(glyf.resolve(8, &maxp).unwrap().points().count(), 3 << 8);
+    assert!(matches!(
+        glyf.resolve(48, &maxp),
+        Err(Error::GlyphTooComplex(48))
+    ));
+}
+
+#[test]
+fn locates_unreadable_glyphs() {
+    let mut glyphs = corpus::simple_glyphs();
+    let glyph_id = glyphs.len() as u16;
+    let start = glyphs
+        .iter()
+        .map(|g| g.len().next_multiple_of(4))
+        .sum::<usize>();
+
+    // one contour but no room for its instructions
+    glyphs.push(vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let bytes = FontSpec::new(glyphs).build();
+    let glyf_offset = table_offset(&bytes, tags::GLYF);
+
+    match read_error(&bytes) {
+        Error::InvalidGlyph {
+            tag,
+            offset,
+            glyph_id: id,
+            source,
+            ..
+        } => {
+            assert_eq!(
+                (tag, offset, id),
+                (tags::GLYF, glyf_offset + start as u64, glyph_id)
+            );
+            assert!(matches!(
+                source.as_deref(),
+                Some(Error::IOError(_) | Error::DecodeError(_))
+            ));
+        }
+        error => panic!("unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn locates_descending_loca_offsets() {
+    let glyphs = corpus::simple_glyphs();
+    let start = glyphs[0].len().next_multiple_of(4) + glyphs[1].len().next_multiple_of(4);
+    let mut bytes = FontSpec::new(glyphs).build();
+    let glyf_offset = table_offset(&bytes, tags::GLYF);
+    let loca_offset = table_offset(&bytes, tags::LOCA) as usize;
+
+    // glyph 2 now ends before it starts
+    let entry = loca_offset + 3 * 2;
+    bytes[entry..entry + 2].copy_from_slice(&(start as u16 / 2 - 1).to_be_bytes());
+
+    match read_error(&bytes) {
+        Error::InvalidGlyph {
+            tag,
+            offset,
+            glyph_id,
+            source,
+            ..
+        } => {
+            assert_eq!(
+                (tag, offset, glyph_id),
+                (tags::GLYF, glyf_offset + start as u64, 2)
+            );
+            assert!(source.is_none());
+        }
+        error => panic!("unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn locates_inconsistent_hmtx() {
+    let glyphs = corpus::simple_glyphs();
+    let num_long_metrics = glyphs.len() as u16 + 1;
+    let bytes = FontSpec {
+        num_long_metrics,
+        ..FontSpec::new(glyphs)
+    }
+    .build();
+
+    match read_error(&bytes) {
+        Error::InvalidTable {
+            tag,
+            offset,
+            source,
+            ..
+        } => {
+            assert_eq!(
+                (tag, offset),
+                (tags::HMTX, table_offset(&bytes, tags::HMTX))
+            );
+            assert!(source.is_none());
+        }
+        error => panic!("unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn locates_unsupported_cmap_subtables() {
+    let mut cmap = corpus::minimal_cmap();
+    cmap[12..14].copy_from_slice(&99u16.to_be_bytes());
+    let bytes = FontSpec::new(corpus::simple_glyphs())
+        .table(tags::CMAP, cmap)
+        .build();
+
+    match read_error(&bytes) {
+        Error::InvalidTable {
+            tag,
+            offset,
+            source,
+            ..
+        } => {
+            assert_eq!(
+                (tag, offset),
+                (tags::CMAP, table_offset(&bytes, tags::CMAP) + 12)
+            );
+            assert!(matches!(
+                source.as_deref(),
+                Some(Error::UnsupportedCmapSubtable(99))
+            ));
+        }
+        error => panic!("unexpected error: {error}"),
+    }
+}
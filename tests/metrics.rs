@@ -0,0 +1,227 @@
+mod common;
+
+use common::{
+    corpus::{self, FontSpec},
+    fixture,
+};
+use fontori::{
+    error::Error,
+    subset::{Subset, TablePolicy},
+    table::{tags, FontTable, GetFontTable, Head, LongVerMetric, Vmtx},
+    ttf::{font::Font, metrics::FontMetrics},
+    utils::reader::TryFromStream,
+};
+use std::io::Cursor;
+
+// fsSelection sits after the fixed size fields, panose and the unicode ranges
+const FS_SELECTION_OFFSET: usize = 62;
+
+fn font(spec: FontSpec) -> Font {
+    Font::try_from_stream(&mut Cursor::new(spec.build())).unwrap()
+}
+
+fn corpus_font() -> Font {
+    font(FontSpec {
+        num_long_metrics: 3,
+        ..FontSpec::new(corpus::simple_glyphs())
+    })
+}
+
+fn vertical_font() -> Font {
+    font(
+        FontSpec::new(corpus::simple_glyphs())
+            .table(tags::VHEA, corpus::vhea(2))
+            .table(tags::VMTX, corpus::vmtx(6, 2)),
+    )
+}
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+#[test]
+fn reads_metrics_past_the_long_metrics() {
+    let font = corpus_font();
+
+    assert_eq!(font.advance_width(1).unwrap(), 510);
+    assert_eq!(font.left_side_bearing(1).unwrap(), -2);
+    assert_eq!(font.advance_width(5).unwrap(), 520);
+    assert_eq!(font.left_side_bearing(5).unwrap(), 2);
+    assert!(matches!(
+        font.advance_width(6),
+        Err(Error::InvalidGlyphId(6))
+    ));
+}
+
+#[test]
+fn derives_right_side_bearing_from_glyph_bounds() {
+    let font = corpus_font();
+
+    // the square spans 0..500 and sits on an advance of 500 with a bearing of -3
+    assert_eq!(font.right_side_bearing(0).unwrap(), 3);
+    assert_eq!(font.right_side_bearing(5).unwrap(), 518);
+    assert_close(font.scaled_right_side_bearing(0, 20.0).unwrap(), 0.06);
+}
+
+#[test]
+fn prefers_typo_metrics_when_requested() {
+    let hhea = font(FontSpec::new(corpus::simple_glyphs()));
+    let typo = font(FontSpec::new(corpus::simple_glyphs()).table(tags::OS_2, corpus::os2(4)));
+
+    let mut os2 = corpus::os2(4);
+    os2[FS_SELECTION_OFFSET + 1] &= !0x80;
+    let win = font(FontSpec::new(corpus::simple_glyphs()).table(tags::OS_2, os2));
+
+    let metrics = |units_per_em, ascent, descent, line_gap| FontMetrics {
+        units_per_em,
+        ascent,
+        descent,
+        line_gap,
+    };
+
+    assert_eq!(hhea.font_metrics().unwrap(), metrics(1000, 900, -200, 50));
+    assert_eq!(typo.font_metrics().unwrap(), metrics(1000, 850, -250, 100));
+    assert_eq!(win.font_metrics().unwrap(), metrics(1000, 900, -200, 50));
+}
+
+#[test]
+fn scales_metrics_to_pixel_size() {
+    let font = corpus_font();
+    let scaled = font.scaled_font_metrics(16.0).unwrap();
+
+    assert_close(scaled.ascent, 14.4);
+    assert_close(scaled.descent, -3.2);
+    assert_close(scaled.line_gap, 0.8);
+    assert_close(font.scaled_advance_width(0, 16.0).unwrap(), 8.0);
+    assert_close(font.scaled_left_side_bearing(5, 500.0).unwrap(), 1.0);
+}
+
+#[test]
+fn matches_hmtx_for_every_glyph() {
+    for name in ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"] {
+        let font = Font::try_from_stream(&mut Cursor::new(fixture(name))).unwrap();
+        let hmtx = font.hmtx().unwrap();
+
+        for glyph_id in 0..font.maxp().unwrap().num_glyphs {
+            let metric = hmtx.metric(glyph_id).unwrap();
+            let advance = font.advance_width(glyph_id).unwrap();
+            let bearing = font.left_side_bearing(glyph_id).unwrap();
+            let width = font.glyf().unwrap().get(glyph_id).map_or(0, |glyph| {
+                i32::from(glyph.header.x_max - glyph.header.x_min)
+            });
+
+            assert_eq!(
+                (advance, bearing),
+                (metric.advance_width, metric.left_side_bearing)
+            );
+            assert_eq!(
+                font.right_side_bearing(glyph_id).unwrap() + i32::from(bearing) + width,
+                i32::from(advance)
+            );
+        }
+    }
+}
+
+#[test]
+fn reads_vertical_metrics() {
+    let font = vertical_font();
+
+    assert_eq!(font.advance_height(1).unwrap(), 990);
+    assert_eq!(font.top_side_bearing(1).unwrap(), 101);
+    assert_eq!(font.advance_height(4).unwrap(), 990);
+    assert_eq!(font.top_side_bearing(4).unwrap(), 104);
+    assert_close(font.scaled_advance_height(0, 16.0).unwrap(), 16.0);
+    assert_close(font.scaled_top_side_bearing(4, 500.0).unwrap(), 52.0);
+    assert!(matches!(
+        font.top_side_bearing(6),
+        Err(Error::InvalidGlyphId(6))
+    ));
+}
+
+#[test]
+fn falls_back_to_typo_metrics_and_head_bounds() {
+    let typo = font(FontSpec::new(corpus::simple_glyphs()).table(tags::OS_2, corpus::os2(4)));
+    let head = font(FontSpec::new(corpus::simple_glyphs()));
+
+    // the square at glyph 0 reaches up to 700
+    assert_eq!(typo.advance_height(0).unwrap(), 1100);
+    assert_eq!(typo.top_side_bearing(0).unwrap(), 150);
+    assert_eq!(head.advance_height(0).unwrap(), 1200);
+    assert_eq!(head.top_side_bearing(0).unwrap(), 200);
+}
+
+#[test]
+fn writes_edited_vertical_metrics_with_consistent_counts() {
+    let mut font = vertical_font();
+    let metrics = (0..6)
+        .map(|glyph_id| LongVerMetric {
+            advance_height: 800 + glyph_id,
+            top_side_bearing: -(glyph_id as i16),
+        })
+        .collect::<Vec<_>>();
+
+    let Some(FontTable::Vmtx(vmtx)) = font.table_mut(tags::VMTX) else {
+        panic!("vmtx table was not parsed");
+    };
+    *vmtx = Vmtx::from_metrics(&metrics);
+
+    let (_, bytes) = font.encode_sfnt().unwrap();
+    let font = Font::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+
+    assert_eq!(font.vhea().unwrap().num_of_long_ver_metrics, 6);
+
+    for metric in metrics {
+        let glyph_id = metric.advance_height - 800;
+        assert_eq!(
+            font.advance_height(glyph_id).unwrap(),
+            metric.advance_height
+        );
+        assert_eq!(
+            font.top_side_bearing(glyph_id).unwrap(),
+            metric.top_side_bearing
+        );
+    }
+}
+
+#[test]
+fn subsets_vertical_metrics() {
+    let mut subset = Subset::new(TablePolicy::Drop);
+    subset.add_glyph_ids([3, 5]);
+
+    let font = subset.apply(vertical_font()).unwrap();
+    let (_, bytes) = font.encode_sfnt().unwrap();
+    let font = Font::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+    let vhea = font.vhea().unwrap();
+
+    assert_eq!(vhea.num_of_long_ver_metrics, 2);
+    assert_eq!(vhea.advance_height_max, 1000);
+
+    for (glyph_id, height, bearing) in [(0, 1000, 100), (1, 990, 103), (2, 990, 105)] {
+        assert_eq!(font.advance_height(glyph_id).unwrap(), height);
+        assert_eq!(font.top_side_bearing(glyph_id).unwrap(), bearing);
+    }
+}
+
+#[test]
+fn refuses_to_scale_without_units_per_em() {
+    let mut font = corpus_font();
+    let head = Head {
+        units_per_em: 0,
+        ..font.head().unwrap().clone()
+    };
+    font.insert_table(tags::HEAD, FontTable::Head(head))
+        .unwrap();
+
+    assert!(matches!(
+        font.scaled_font_metrics(16.0),
+        Err(Error::InvalidUnitsPerEm)
+    ));
+    assert!(matches!(
+        font.scaled_advance_width(0, 16.0),
+        Err(Error::InvalidUnitsPerEm)
+    ));
+    assert!(matches!(
+        font.font_metrics().unwrap().scale(16.0),
+        Err(Error::InvalidUnitsPerEm)
+    ));
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::fixture;
+use fontori::{
+    table::{GetFontTable, Name, NameId, NameRecord, PLATFORM_WINDOWS},
+    ttf::font::Font,
+    utils::{bincode::encode_to_vec, reader::TryFromStream},
+};
+use std::io::Cursor;
+
+fn record(name_id: u16, value: Vec<u8>) -> NameRecord {
+    NameRecord {
+        platform_id: PLATFORM_WINDOWS,
+        encoding_id: 1,
+        language_id: 0x409,
+        name_id,
+        length: 0,
+        offset: 0,
+        value: value.into(),
+    }
+}
+
+fn name(records: Vec<NameRecord>) -> Name {
+    Name {
+        format: 0,
+        count: 0,
+        string_offset: 0,
+        name_records: records.into(),
+        lang_tag_count: 0,
+        lang_tag_records: Vec::new().into(),
+    }
+}
+
+#[test]
+fn reads_fixture_names() {
+    let font = Font::try_from_stream(&mut Cursor::new(fixture("OpenSans-Regular.woff2"))).unwrap();
+    let name = font.name().unwrap();
+
+    assert_eq!(
+        name.get_any(NameId::FamilyName).as_deref(),
+        Some("Open Sans")
+    );
+    assert_eq!(
+        name.get(NameId::SubfamilyName, PLATFORM_WINDOWS, 0x409)
+            .as_deref(),
+        Some("Regular")
+    );
+}
+
+#[test]
+fn rebuilds_string_storage_for_edited_records() {
+    let value = "Family".encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let bytes = encode_to_vec(name(vec![record(1, value)])).unwrap();
+    let parsed = Name::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+
+    assert_eq!(
+        parsed.get_any(NameId::FamilyName).as_deref(),
+        Some("Family")
+    );
+}
+
+#[test]
+fn refuses_string_storage_past_16_bit_offsets() {
+    let fits = (0..3).map(|i| record(i, vec![i as u8; 30000])).collect();
+    assert!(encode_to_vec(name(fits)).is_ok());
+
+    let overflows = (0..4).map(|i| record(i, vec![i as u8; 30000])).collect();
+    assert!(encode_to_vec(name(overflows)).is_err());
+
+    let too_long = vec![record(0, vec![0; 0x10000])];
+    assert!(encode_to_vec(name(too_long)).is_err());
+}
+
+#[test]
+fn refuses_more_records_than_the_header_can_address() {
+    let records = |count: u16| (0..count).map(|i| record(i, Vec::new())).collect();
+
+    assert!(encode_to_vec(name(records(5000))).is_ok());
+    assert!(encode_to_vec(name(records(5500))).is_err());
+}
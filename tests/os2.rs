@@ -0,0 +1,51 @@
+mod common;
+
+use common::corpus::{self, FontSpec};
+use fontori::{
+    table::{tags, EmbeddingPermission, FsType, GetFontTable},
+    ttf::font::Font,
+    utils::reader::TryFromStream,
+};
+use std::io::Cursor;
+
+// fsType follows the version, average width, weight and width classes
+const FS_TYPE_OFFSET: usize = 8;
+
+#[test]
+fn grants_the_least_restrictive_embedding_permission() {
+    let cases = [
+        (0x0000, EmbeddingPermission::Installable),
+        (0x0002, EmbeddingPermission::Restricted),
+        (0x0004, EmbeddingPermission::PreviewAndPrint),
+        (0x0008, EmbeddingPermission::Editable),
+        (0x0006, EmbeddingPermission::PreviewAndPrint),
+        (0x000A, EmbeddingPermission::Editable),
+        (0x000C, EmbeddingPermission::Editable),
+        (0x030E, EmbeddingPermission::Editable),
+    ];
+
+    for (fs_type, permission) in cases {
+        assert_eq!(
+            FsType(fs_type).embedding_permission(),
+            permission,
+            "{fs_type:#06x}"
+        );
+    }
+}
+
+#[test]
+fn reads_embedding_flags_from_the_table() {
+    let mut os2 = corpus::os2(4);
+    os2[FS_TYPE_OFFSET..FS_TYPE_OFFSET + 2].copy_from_slice(&0x0306u16.to_be_bytes());
+
+    let spec = FontSpec::new(corpus::simple_glyphs()).table(tags::OS_2, os2);
+    let font = Font::try_from_stream(&mut Cursor::new(spec.build())).unwrap();
+    let fs_type = font.os2().unwrap().fs_type();
+
+    assert_eq!(
+        fs_type.embedding_permission(),
+        EmbeddingPermission::PreviewAndPrint
+    );
+    assert!(fs_type.no_subsetting());
+    assert!(fs_type.bitmap_only());
+}
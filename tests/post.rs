@@ -0,0 +1,92 @@
+mod common;
+
+use common::corpus;
+use fontori::{
+    table::{Post, PostV2, MAC_GLYPH_NAMES},
+    utils::bincode::encode_to_vec,
+};
+use std::io::Cursor;
+
+const VERSION_1_0: u32 = 0x0001_0000;
+const VERSION_2_0: u32 = 0x0002_0000;
+const VERSION_2_5: u32 = 0x0002_5000;
+const VERSION_3_0: u32 = 0x0003_0000;
+
+fn post(bytes: &[u8]) -> Post {
+    Post::try_from_params(bytes.len() as u32, &mut Cursor::new(bytes)).unwrap()
+}
+
+#[test]
+fn reads_the_header() {
+    let post = post(&corpus::post(VERSION_3_0, 6));
+
+    assert_eq!(post.italic_angle(), -11.5);
+    assert!(!post.is_fixed_pitch());
+    assert_eq!(post.num_glyph_names(), 0);
+    assert_eq!(post.glyph_name(0), None);
+}
+
+#[test]
+fn names_glyphs_from_the_standard_order() {
+    let post = post(&corpus::post(VERSION_1_0, 6));
+
+    assert_eq!(post.num_glyph_names(), MAC_GLYPH_NAMES.len());
+    assert_eq!(post.glyph_name(3), Some("space"));
+    assert_eq!(post.glyph_name(36), Some("A"));
+    assert_eq!(post.glyph_name(258), None);
+    assert_eq!(post.glyph_index("A"), Some(36));
+}
+
+#[test]
+fn separates_standard_and_custom_names() {
+    let post = post(&corpus::post(VERSION_2_0, 8));
+
+    assert_eq!(post.num_glyph_names(), 8);
+    assert_eq!(post.glyph_name(0), Some(".notdef"));
+    assert_eq!(post.glyph_name(3), Some("space"));
+    assert_eq!(post.glyph_name(1), Some("name0"));
+    assert_eq!(post.glyph_name(4), Some("name1"));
+    assert_eq!(post.glyph_name(8), None);
+    assert_eq!(post.glyph_index("space"), Some(3));
+    assert_eq!(post.glyph_index("name1"), Some(4));
+    assert_eq!(post.glyph_index("name9"), None);
+}
+
+#[test]
+fn offsets_version_2_5_names() {
+    let post = post(&corpus::post(VERSION_2_5, 6));
+
+    assert_eq!(post.num_glyph_names(), 6);
+    assert_eq!(post.glyph_name(0), Some(".notdef"));
+    assert_eq!(post.glyph_name(1), Some("nonmarkingreturn"));
+    assert_eq!(post.glyph_name(2), Some("exclam"));
+    assert_eq!(post.glyph_index("exclam"), Some(2));
+}
+
+#[test]
+fn encodes_every_version_losslessly() {
+    for version in [VERSION_1_0, VERSION_2_0, VERSION_2_5, VERSION_3_0] {
+        let bytes = corpus::post(version, 9);
+        assert_eq!(encode_to_vec(post(&bytes)).unwrap(), bytes, "{version:#x}");
+    }
+}
+
+#[test]
+fn builds_version_2_names() {
+    let long = "é".repeat(200);
+    let names = ["space", "custom", "custom", "A", long.as_str()];
+
+    let mut post = post(&corpus::post(VERSION_3_0, 5));
+    post.set_glyph_names(PostV2::from_names(names));
+    let post = self::post(&encode_to_vec(&post).unwrap());
+
+    assert_eq!(post.num_glyph_names(), 5);
+    assert_eq!(post.glyph_name(0), Some("space"));
+    assert_eq!(post.glyph_name(1), Some("custom"));
+    assert_eq!(post.glyph_name(2), Some("custom"));
+    assert_eq!(post.glyph_name(3), Some("A"));
+    assert_eq!(post.glyph_index("custom"), Some(1));
+
+    // 255 bytes would end half way through a two byte character
+    assert_eq!(post.glyph_name(4), Some(&long[..254]));
+}
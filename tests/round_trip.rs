@@ -0,0 +1,129 @@
+mod common;
+
+use common::{
+    corpus::{self, FontSpec},
+    fixture, outlines,
+};
+use fontori::{
+    error::Error,
+    table::{
+        glyph::GlyphData,
+        tags::{self, tag_to_string},
+        FontTable, GetFontTable, Loca, LocaFormat,
+    },
+    ttf::{font::Font, font_ref::FontRef},
+    utils::{bincode::encode_to_vec, reader::TryFromStream, types::Opt},
+};
+use std::io::Cursor;
+
+fn assert_tables_round_trip(name: &str, bytes: &[u8]) {
+    let font = Font::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+    let font_ref = FontRef::new(bytes).unwrap();
+    let encoded = font.encode_tables().unwrap();
+
+    for tag in font_ref.tags() {
+        let mut original = font_ref.table_data(tag).unwrap().to_vec();
+
+        // checksumAdjustment is recomputed when the font is written
+        if tag == tags::HEAD {
+            original[8..12].fill(0);
+        }
+
+        assert!(
+            encoded[&tag] == original,
+            "{name}: '{}' table changed when written back",
+            tag_to_string(tag)
+        );
+    }
+}
+
+#[test]
+fn corpus_tables_round_trip() {
+    for (name, bytes) in corpus::fonts() {
+        assert_tables_round_trip(name, &bytes);
+    }
+}
+
+#[test]
+fn fixture_tables_round_trip() {
+    for name in ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"] {
+        let font = Font::try_from_stream(&mut Cursor::new(fixture(name))).unwrap();
+        let (_, bytes) = font.encode_sfnt().unwrap();
+
+        assert_tables_round_trip(name, &bytes);
+
+        let reparsed = Font::try_from_stream(&mut Cursor::new(&bytes)).unwrap();
+        assert_eq!(reparsed.encode_sfnt().unwrap().1, bytes, "{name}");
+    }
+}
+
+#[test]
+fn widens_a_short_loca_that_no_longer_fits() {
+    let bytes = FontSpec::new(corpus::simple_glyphs()).build();
+    let mut font = Font::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(font.loca().unwrap().format, LocaFormat::Short);
+
+    // long instructions push the later glyphs past the last short offset
+    let Some(FontTable::Glyf(glyf)) = font.table_mut(tags::GLYF) else {
+        panic!("glyf table was not parsed");
+    };
+    let glyphs = std::mem::take(&mut glyf.glyphs);
+    glyf.glyphs = glyphs
+        .into_iter()
+        .map(|glyph| match glyph {
+            Opt::Some(mut glyph) => {
+                if let GlyphData::Simple(simple) = &mut glyph.data {
+                    simple.instruction_length = 40000;
+                    simple.instructions = vec![0xB0; 40000].into();
+                }
+
+                Opt::Some(glyph)
+            }
+            Opt::None => Opt::None,
+        })
+        .collect();
+
+    let expected = outlines(&font);
+    let (_, bytes) = font.encode_sfnt().unwrap();
+    let font = Font::try_from_stream(&mut Cursor::new(bytes)).unwrap();
+
+    assert_eq!(font.head().unwrap().index_to_loc_format, 1);
+    assert_eq!(font.loca().unwrap().format, LocaFormat::Long);
+    assert_eq!(outlines(&font), expected);
+}
+
+#[test]
+fn refuses_to_write_a_truncated_short_loca() {
+    for offsets in [vec![0, 0x1FFFE, 0x20000], vec![0, 3]] {
+        let loca = Loca {
+            offsets: offsets.into(),
+            format: LocaFormat::Short,
+        };
+
+        assert!(encode_to_vec(&loca).is_err());
+    }
+}
+
+#[test]
+fn rejects_tables_inserted_under_another_tag() {
+    let mut font =
+        Font::try_from_stream(&mut Cursor::new(fixture("OpenSans-Regular.woff2"))).unwrap();
+    let head = match font.table(tags::HEAD) {
+        Some(FontTable::Head(head)) => head.clone(),
+        _ => panic!("missing head"),
+    };
+
+    assert!(matches!(
+        font.insert_table(tags::CMAP, FontTable::Head(head)),
+        Err(Error::MismatchedTable(tags::CMAP))
+    ));
+    assert!(matches!(
+        font.insert_table(tags::GLYF, FontTable::Other(vec![0; 4].into())),
+        Err(Error::MismatchedTable(tags::GLYF))
+    ));
+    assert!(matches!(
+        font.insert_table(tags::VMTX, FontTable::Other(vec![0; 4].into())),
+        Err(Error::MismatchedTable(tags::VMTX))
+    ));
+    assert!(matches!(font.table(tags::CMAP), Some(FontTable::Cmap(_))));
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use common::{
+    corpus::{self, Bytes, FontSpec},
+    outlines,
+};
+use fontori::{
+    subset::{Subset, TablePolicy},
+    table::{glyph::GlyphData, tags, Cmap, CmapSubtable, FontTable, GetFontTable, LocaFormat},
+    ttf::font::Font,
+    utils::{bincode::encode_to_vec, reader::TryFromStream},
+};
+use std::{collections::BTreeMap, io::Cursor};
+
+const ARGS_ARE_WORDS_AND_XY_VALUES: u16 = 0x0003;
+const MORE_COMPONENTS: u16 = 0x0020;
+
+fn font(spec: FontSpec) -> Font {
+    Font::try_from_stream(&mut Cursor::new(spec.build())).unwrap()
+}
+
+fn reload(font: Font) -> Font {
+    let (_, bytes) = font.encode_sfnt().unwrap();
+    Font::try_from_stream(&mut Cursor::new(bytes)).unwrap()
+}
+
+fn compound(components: &[u16]) -> Vec<u8> {
+    let mut glyph = Bytes::default();
+    glyph.i16(-1).i16(-50).i16(-300).i16(1200).i16(900);
+
+    for (i, &component) in components.iter().enumerate() {
+        let more = if i + 1 < components.len() {
+            MORE_COMPONENTS
+        } else {
+            0
+        };
+
+        glyph
+            .u16(ARGS_ARE_WORDS_AND_XY_VALUES | more)
+            .u16(component);
+        glyph.i16(i as i16 * 40).i16(-(i as i16) * 15);
+    }
+
+    glyph.0
+}
+
+// glyph 7 nests glyph 6, which combines two simple glyphs, glyph 8 wraps the square
+fn compound_font() -> Font {
+    let mut glyphs = corpus::simple_glyphs();
+    glyphs.extend([compound(&[1, 2]), compound(&[6, 5]), compound(&[0])]);
+
+    let mut font = font(FontSpec::new(glyphs));
+    let mappings = BTreeMap::from([(0x41, 8), (0x42, 3), (0x43, 7)]);
+    font.insert_table(
+        tags::CMAP,
+        FontTable::Cmap(Cmap::from_mappings(&mappings).unwrap()),
+    )
+    .unwrap();
+    font
+}
+
+fn components(font: &Font, glyph_id: u16) -> Vec<u16> {
+    match &font.glyf().unwrap().get(glyph_id).unwrap().data {
+        GlyphData::Compound(compound) => {
+            compound.components.iter().map(|c| c.glyph_index).collect()
+        }
+        GlyphData::Simple(_) => Vec::new(),
+    }
+}
+
+#[test]
+fn maps_the_terminal_code_point_to_notdef() {
+    let mappings = BTreeMap::from([(0x41, 3), (0x42, 4), (0xFFFE, 9)]);
+    let cmap = Cmap::from_mappings(&mappings).unwrap();
+
+    assert_eq!(cmap.glyph_index('A'), Some(3));
+    assert_eq!(cmap.glyph_index('\u{FFFE}'), Some(9));
+    assert_eq!(cmap.glyph_index('\u{FFFF}'), None);
+    assert_eq!(cmap.mappings().collect::<BTreeMap<_, _>>(), mappings);
+}
+
+// every other code point, so each mapping needs a format 4 segment of its own
+fn scattered(count: u32) -> BTreeMap<u32, u16> {
+    (0..count)
+        .map(|i| (0x4E00 + i * 2, (i * 7 % 60000) as u16 + 1))
+        .collect()
+}
+
+#[test]
+fn falls_back_to_format_12_when_format_4_overflows() {
+    for (count, has_format_4) in [(3000, true), (9000, false)] {
+        let mappings = scattered(count);
+        let cmap = Cmap::from_mappings(&mappings).unwrap();
+        let reparsed =
+            Cmap::try_from_stream(&mut Cursor::new(encode_to_vec(&cmap).unwrap())).unwrap();
+        let formats = reparsed
+            .cmap_subtables
+            .iter()
+            .map(|s| matches!(s, CmapSubtable::Format4(_)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(formats.contains(&true), has_format_4, "{count}");
+        assert_eq!(reparsed.mappings().collect::<BTreeMap<_, _>>(), mappings);
+        assert_eq!(
+            reparsed.glyph_index('\u{4E02}'),
+            mappings.get(&0x4E02).copied()
+        );
+    }
+}
+
+#[test]
+fn keeps_loca_offsets_in_memory() {
+    let mut subset = Subset::new(TablePolicy::Drop);
+    subset.add_glyph_ids([2, 4]);
+
+    let font = subset
+        .apply(font(FontSpec::new(corpus::simple_glyphs())))
+        .unwrap();
+    let offsets = font.glyf().unwrap().offsets().unwrap();
+
+    assert_eq!(font.loca().unwrap().offsets.as_slice(), offsets.as_slice());
+    assert_eq!(offsets.as_slice().len(), 4);
+}
+
+#[test]
+fn drops_glyph_dependent_tables_when_passing_through() {
+    let dependent = [tags::GVAR, tags::HVAR, tags::COLR, tags::CBDT, tags::SBIX];
+    let spec = dependent
+        .iter()
+        .fold(FontSpec::new(corpus::simple_glyphs()), |spec, &tag| {
+            spec.table(tag, vec![0; 8])
+        })
+        .table(tags::GASP, vec![0; 4]);
+
+    let mut subset = Subset::new(TablePolicy::PassThrough);
+    subset.add_glyph_ids([1]);
+    let font = subset.apply(font(spec)).unwrap();
+
+    assert!(font.table(tags::GASP).is_some());
+
+    for tag in dependent {
+        assert!(font.table(tag).is_none(), "{}", tags::tag_to_string(tag));
+    }
+}
+
+#[test]
+fn closes_over_components_and_renumbers_them() {
+    let original = compound_font();
+    let expected = outlines(&original);
+
+    let mut subset = Subset::new(TablePolicy::Drop);
+    subset.add_chars(['A', 'C']);
+    let font = reload(subset.apply(compound_font()).unwrap());
+    let resolved = outlines(&font);
+
+    // old glyphs 0, 1, 2, 5, 6, 7 and 8 keep their order
+    assert_eq!(font.maxp().unwrap().num_glyphs, 7);
+    assert_eq!(components(&font, 4), [1, 2]);
+    assert_eq!(components(&font, 5), [4, 3]);
+    assert_eq!(components(&font, 6), [0]);
+
+    for (new_id, old_id) in [0, 1, 2, 5, 6, 7, 8].into_iter().enumerate() {
+        assert_eq!(resolved[new_id], expected[old_id]);
+    }
+}
+
+#[test]
+fn rewrites_cmap_to_the_new_glyph_ids() {
+    let mut subset = Subset::new(TablePolicy::Drop);
+    subset.add_chars(['A', 'C', 'Z']);
+    let font = reload(subset.apply(compound_font()).unwrap());
+    let cmap = font.cmap().unwrap();
+
+    assert_eq!(
+        cmap.mappings().collect::<BTreeMap<_, _>>(),
+        BTreeMap::from([(0x41, 6), (0x43, 5)])
+    );
+    assert_eq!(cmap.glyph_index('B'), None);
+}
+
+#[test]
+fn keeps_variation_sequences_of_the_kept_chars() {
+    let mut glyphs = corpus::simple_glyphs();
+    glyphs.resize(40, glyphs[0].clone());
+    let spec = FontSpec::new(glyphs).table(tags::CMAP, corpus::cmap());
+
+    let mut subset = Subset::new(TablePolicy::Drop);
+    subset.add_chars(['0', '1', '2']);
+    let font = reload(subset.apply(font(spec)).unwrap());
+    let cmap = font.cmap().unwrap();
+
+    // old glyphs 2 and 3 only come in through the variation sequences
+    assert_eq!(font.maxp().unwrap().num_glyphs, 6);
+    assert_eq!(cmap.variant_glyph('1', '\u{FE01}'), Some(1));
+    assert_eq!(cmap.variant_glyph('2', '\u{FE01}'), Some(2));
+    assert_eq!(cmap.variant_glyph('0', '\u{FE00}'), Some(3));
+    assert_eq!(cmap.variant_glyph('5', '\u{FE00}'), None);
+    assert_eq!(cmap.variant_glyph('\u{9089}', '\u{E0100}'), None);
+}
+
+#[test]
+fn recomputes_maxp_maxima() {
+    let mut subset = Subset::new(TablePolicy::Drop);
+    subset.add_chars(['C']);
+    let font = reload(subset.apply(compound_font()).unwrap());
+    let maxp = font.maxp().unwrap();
+
+    assert_eq!((maxp.max_points, maxp.max_contours), (10, 2));
+    assert_eq!(
+        (maxp.max_component_points, maxp.max_component_contours),
+        (17, 4)
+    );
+    assert_eq!(maxp.max_size_of_instructions, 3);
+    assert_eq!(maxp.max_component_elements, 2);
+    assert_eq!(maxp.max_component_depth, 2);
+    assert_eq!(maxp.max_storage, 16);
+}
+
+#[test]
+fn picks_the_smallest_loca_format() {
+    let mut glyphs = corpus::simple_glyphs();
+    glyphs.resize(206, glyphs[4].clone());
+
+    let bytes = FontSpec {
+        long_loca: true,
+        ..FontSpec::new(glyphs)
+    }
+    .build();
+
+    for (glyph_ids, format) in [(0..3, LocaFormat::Short), (0..206, LocaFormat::Long)] {
+        let mut subset = Subset::new(TablePolicy::Drop);
+        subset.add_glyph_ids(glyph_ids);
+        let font = Font::try_from_stream(&mut Cursor::new(&bytes)).unwrap();
+        let font = reload(subset.apply(font).unwrap());
+
+        assert_eq!(font.loca().unwrap().format, format);
+        assert_eq!(
+            font.head().unwrap().index_to_loc_format,
+            (format == LocaFormat::Long) as i16
+        );
+    }
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{
+    corpus::{self, FontSpec},
+    fixture,
+};
+use fontori::{
+    table::tags::{self, Tag},
+    ttf::{font::Font, font_ref::FontRef},
+    utils::reader::TryFromStream,
+    validate::{validate, Issue, Severity},
+};
+use std::io::Cursor;
+
+const FIXTURES: [&str; 2] = ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"];
+
+fn sfnt(name: &str) -> Vec<u8> {
+    let font = Font::try_from_stream(&mut Cursor::new(fixture(name))).unwrap();
+    font.encode_sfnt().unwrap().1
+}
+
+fn table_offset(bytes: &[u8], tag: Tag) -> usize {
+    let font = FontRef::new(bytes).unwrap();
+    let entries = font.font_directory().table_directory.as_slice();
+    entries.iter().find(|e| e.tag == tag).unwrap().offset as usize
+}
+
+fn errors(bytes: &[u8]) -> Vec<Issue> {
+    let issues = validate(&FontRef::new(bytes).unwrap());
+    issues
+        .into_iter()
+        .filter(|i| i.severity == Severity::Error)
+        .collect()
+}
+
+#[test]
+fn accepts_well_formed_fonts() {
+    for name in FIXTURES {
+        let bytes = sfnt(name);
+        assert_eq!(validate(&FontRef::new(&bytes).unwrap()), []);
+    }
+}
+
+#[test]
+fn reports_a_wrong_magic_number() {
+    let mut bytes = sfnt(FIXTURES[0]);
+    let head = table_offset(&bytes, tags::HEAD);
+    bytes[head + 12..head + 16].fill(0);
+
+    let errors = errors(&bytes);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].tag, tags::HEAD);
+    assert_eq!(errors[0].offset, head as u64 + 12);
+}
+
+#[test]
+fn reports_loca_offsets_beyond_glyf() {
+    let mut bytes = sfnt(FIXTURES[1]);
+    let loca = table_offset(&bytes, tags::LOCA);
+    bytes[loca + 40..loca + 44].copy_from_slice(&u32::MAX.to_be_bytes());
+
+    let errors = errors(&bytes);
+    let beyond = errors
+        .iter()
+        .find(|i| i.description == "loca offset beyond glyf length")
+        .unwrap();
+
+    assert_eq!(beyond.tag, tags::LOCA);
+    assert_eq!(beyond.offset, loca as u64 + 40);
+    assert_eq!(beyond.glyph_id, Some(9));
+}
+
+#[test]
+fn reports_maxp_maxima_that_are_too_small() {
+    let mut bytes = sfnt(FIXTURES[1]);
+    let maxp = table_offset(&bytes, tags::MAXP);
+    bytes[maxp + 6..maxp + 8].copy_from_slice(&1u16.to_be_bytes());
+
+    let errors = errors(&bytes);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].tag, tags::MAXP);
+    assert_eq!(errors[0].offset, maxp as u64 + 6);
+}
+
+#[test]
+fn reports_overlapping_tables() {
+    let mut bytes = sfnt(FIXTURES[0]);
+    let font = FontRef::new(&bytes).unwrap();
+    let entries = font.font_directory().table_directory.as_slice();
+    let index = entries.iter().position(|e| e.tag == tags::POST).unwrap();
+    let glyf = table_offset(&bytes, tags::GLYF) as u32;
+    let position = 12 + index * 16 + 8;
+    bytes[position..position + 4].copy_from_slice(&glyf.to_be_bytes());
+
+    let errors = errors(&bytes);
+    assert!(errors
+        .iter()
+        .any(|i| i.tag == tags::POST && i.description == "table overlaps 'glyf'"));
+}
+
+#[test]
+fn reports_vertical_metric_counts_beyond_num_glyphs() {
+    let bytes = FontSpec::new(corpus::simple_glyphs())
+        .table(tags::VHEA, corpus::vhea(7))
+        .table(tags::VMTX, corpus::vmtx(6, 6))
+        .build();
+    let vhea = table_offset(&bytes, tags::VHEA);
+
+    let errors = errors(&bytes);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].tag, tags::VHEA);
+    assert_eq!(errors[0].offset, vhea as u64 + 34);
+    assert_eq!(
+        errors[0].description,
+        "vhea.numOfLongVerMetrics exceeds maxp.numGlyphs"
+    );
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::{fixture, outlines};
+use fontori::{
+    ttf::font::Font,
+    utils::{bincode::encode_to_vec, reader::TryFromStream},
+    woff::{woff_dir::WoffDirectory, Woff, WoffOptions, WOFF_SIGNATURE},
+};
+use std::io::Cursor;
+
+const FIXTURES: [&str; 2] = ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"];
+
+fn parse<T: TryFromStream>(bytes: &[u8]) -> T {
+    T::try_from_stream(&mut Cursor::new(bytes)).unwrap()
+}
+
+fn encode(font: Font, options: WoffOptions) -> Vec<u8> {
+    let woff = Woff {
+        options,
+        ..Woff::new(font)
+    };
+
+    encode_to_vec(woff).unwrap()
+}
+
+#[test]
+fn round_trip_preserves_tables() {
+    for name in FIXTURES {
+        let font: Font = parse(&fixture(name));
+        let expected = outlines(&font);
+        let sfnt = encode_to_vec(&font).unwrap();
+
+        let bytes = encode(font, WoffOptions::default());
+        let decoded: Woff = parse(&bytes);
+
+        assert_eq!(outlines(&decoded.font), expected);
+        assert_eq!(encode_to_vec(&decoded.font).unwrap(), sfnt);
+        assert_eq!(decoded.font.verify_checksums().unwrap(), []);
+    }
+}
+
+#[test]
+fn compression_level_changes_only_the_size() {
+    let font: Font = parse(&fixture(FIXTURES[1]));
+    let sfnt = encode_to_vec(&font).unwrap();
+    let stored = encode(
+        parse(&fixture(FIXTURES[1])),
+        WoffOptions {
+            compression_level: 0,
+            ..Default::default()
+        },
+    );
+    let best = encode(
+        font,
+        WoffOptions {
+            compression_level: 9,
+            ..Default::default()
+        },
+    );
+
+    // a stored zlib stream is larger than its input, so every table is written uncompressed
+    let directory: WoffDirectory = parse(&stored);
+    assert!(directory
+        .table_directory
+        .iter()
+        .all(|e| e.comp_length == e.orig_length));
+    assert!(best.len() < stored.len());
+
+    for bytes in [stored, best] {
+        let decoded: Woff = parse(&bytes);
+        assert_eq!(encode_to_vec(&decoded.font).unwrap(), sfnt);
+    }
+}
+
+#[test]
+fn preserves_metadata_and_private_data() {
+    let woff = Woff {
+        metadata: Some(b"<?xml version=\"1.0\"?><metadata version=\"1.0\"/>".to_vec()),
+        private_data: Some(vec![1, 2, 3]),
+        options: WoffOptions {
+            major_version: 1,
+            minor_version: 2,
+            pad_end: false,
+            ..Default::default()
+        },
+        ..Woff::new(parse(&fixture(FIXTURES[0])))
+    };
+
+    let bytes = encode_to_vec(&woff).unwrap();
+    let directory: WoffDirectory = parse(&bytes);
+    let decoded: Woff = parse(&bytes);
+
+    assert_eq!(directory.header.length as usize, bytes.len());
+    assert_eq!(bytes.len() % 4, 3);
+    assert!(directory.header.meta_length < directory.header.meta_orig_length);
+    assert_eq!(decoded.metadata, woff.metadata);
+    assert_eq!(decoded.private_data, woff.private_data);
+    assert_eq!(decoded.options.major_version, 1);
+    assert_eq!(decoded.options.minor_version, 2);
+}
+
+#[test]
+fn font_detects_the_woff_signature() {
+    let bytes = encode(parse(&fixture(FIXTURES[0])), WoffOptions::default());
+    let woff: Woff = parse(&bytes);
+    let font: Font = parse(&bytes);
+
+    assert_eq!(bytes[..4], WOFF_SIGNATURE.to_be_bytes());
+    assert_eq!(
+        encode_to_vec(&font).unwrap(),
+        encode_to_vec(&woff.font).unwrap()
+    );
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use common::{fixture, outlines};
+use fontori::{
+    error::Error,
+    table::{tags, GetFontTable, LocaFormat},
+    ttf::{checksum::ChecksumMismatch, font::Font},
+    utils::{bincode::encode_to_vec, reader::TryFromStream},
+    woff2::{Woff2, Woff2Options},
+};
+use std::io::Cursor;
+
+const FIXTURES: [&str; 2] = ["OpenSans-Regular.woff2", "SourceCodePro-Medium.woff2"];
+
+const FAST: Woff2Options = Woff2Options {
+    quality: 4,
+    transform_glyf: true,
+    transform_hmtx: true,
+    major_version: 0,
+    minor_version: 0,
+};
+
+fn parse<T: TryFromStream>(bytes: &[u8]) -> T {
+    T::try_from_stream(&mut Cursor::new(bytes)).unwrap()
+}
+
+fn encode(font: Font, options: Woff2Options) -> Vec<u8> {
+    let woff2 = Woff2 {
+        options,
+        ..Woff2::new(font)
+    };
+
+    encode_to_vec(woff2).unwrap()
+}
+
+#[test]
+fn decodes_to_a_valid_sfnt() {
+    for name in FIXTURES {
+        let font: Font = parse(&fixture(name));
+        let mismatches = font.verify_checksums().unwrap();
+
+        assert!(!mismatches.iter().any(|m| matches!(
+            m,
+            ChecksumMismatch::Table {
+                tag: tags::HEAD | tags::GLYF | tags::LOCA | tags::HMTX,
+                ..
+            }
+        )));
+        assert_eq!(
+            font.glyf().unwrap().num_glyphs(),
+            font.maxp().unwrap().num_glyphs as usize
+        );
+    }
+}
+
+#[test]
+fn reconstructs_both_loca_formats() {
+    let short: Font = parse(&fixture(FIXTURES[0]));
+    let long: Font = parse(&fixture(FIXTURES[1]));
+
+    assert_eq!(short.loca().unwrap().format, LocaFormat::Short);
+    assert_eq!(long.loca().unwrap().format, LocaFormat::Long);
+}
+
+#[test]
+fn woff2_matches_the_decoded_sfnt() {
+    for name in FIXTURES {
+        let font: Font = parse(&fixture(name));
+        let sfnt = encode_to_vec(&font).unwrap();
+        let from_sfnt: Font = parse(&sfnt);
+
+        assert_eq!(outlines(&font), outlines(&from_sfnt));
+        assert_eq!(encode_to_vec(&from_sfnt).unwrap(), sfnt);
+    }
+}
+
+#[test]
+fn transformed_round_trip_preserves_outlines() {
+    for name in FIXTURES {
+        let font: Font = parse(&fixture(name));
+        let expected = outlines(&font);
+        let hmtx = encode_to_vec(font.hmtx().unwrap()).unwrap();
+        let sfnt = encode_to_vec(&font).unwrap();
+
+        let bytes = encode(font, FAST);
+        let decoded: Font = parse(&bytes);
+
+        assert_eq!(outlines(&decoded), expected);
+        assert_eq!(encode_to_vec(decoded.hmtx().unwrap()).unwrap(), hmtx);
+        assert_eq!(encode_to_vec(&decoded).unwrap(), sfnt);
+    }
+}
+
+#[test]
+fn untransformed_round_trip_preserves_tables() {
+    let font: Font = parse(&fixture(FIXTURES[0]));
+    let sfnt = encode_to_vec(&font).unwrap();
+    let options = Woff2Options {
+        transform_glyf: false,
+        ..FAST
+    };
+
+    let decoded: Font = parse(&encode(font, options));
+
+    assert_eq!(encode_to_vec(&decoded).unwrap(), sfnt);
+}
+
+#[test]
+fn preserves_metadata_and_private_data() {
+    let woff2 = Woff2 {
+        metadata: Some(b"<?xml version=\"1.0\"?><metadata version=\"1.0\"/>".to_vec()),
+        private_data: Some(vec![1, 2, 3]),
+        options: Woff2Options {
+            major_version: 1,
+            minor_version: 2,
+            ..FAST
+        },
+        ..Woff2::new(parse(&fixture(FIXTURES[0])))
+    };
+
+    let bytes = encode_to_vec(&woff2).unwrap();
+    let decoded: Woff2 = parse(&bytes);
+
+    assert_eq!(bytes.len() % 4, 0);
+    assert_eq!(decoded.metadata, woff2.metadata);
+    assert_eq!(decoded.private_data, woff2.private_data);
+    assert_eq!(decoded.options.major_version, 1);
+    assert_eq!(decoded.options.minor_version, 2);
+}
+
+#[test]
+fn rejects_truncated_data() {
+    let bytes = fixture(FIXTURES[0]);
+
+    for length in [4, 48, 200, bytes.len() / 2] {
+        let result = Font::try_from_stream(&mut Cursor::new(&bytes[..length]));
+        assert!(result.is_err());
+    }
+
+    let mut corrupt = bytes.clone();
+    corrupt[100..400].fill(0xAA);
+    assert!(matches!(
+        Font::try_from_stream(&mut Cursor::new(&corrupt)),
+        Err(Error::InvalidWoff2Data(_) | Error::IOError(_) | Error::DecodeError(_))
+    ));
+}